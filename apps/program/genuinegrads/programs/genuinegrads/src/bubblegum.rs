@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+
+use crate::states::UniversityTree;
+use crate::utils::{current_merkle_root, leaf_data_hash};
+
+// Bubblegum v2 CPI
+use mpl_bubblegum::hash::hash_creators;
+use mpl_bubblegum::instructions::{
+    BurnV2CpiBuilder, FreezeV2CpiBuilder, MintV2CpiBuilder, SetNonTransferableV2CpiBuilder,
+    ThawV2CpiBuilder, TransferV2CpiBuilder, UpdateMetadataV2CpiBuilder,
+};
+use mpl_bubblegum::types::{MetadataArgsV2, UpdateArgs};
+
+/// Accounts shared by the Bubblegum V2 CPIs on a university tree. `authority` is the
+/// university authority: payer, tree delegate, collection update authority and the
+/// collection's permanent burn/freeze delegate, so the student never signs. For
+/// transfers it is only the payer; a program PDA signs as the transfer authority.
+pub struct TreeCpi<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub core_collection: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// An existing leaf as proven by DAS getAssetWithProof.
+pub struct LeafProof {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    /// Optional Bubblegum v2 extras (current leaf flags, e.g. non-transferable / frozen)
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+}
+
+/// Proof path accounts as (account_info, is_writable, is_signer)
+fn proof_accounts<'a, 'info>(proof: &'a [AccountInfo<'info>]) -> Vec<(&'a AccountInfo<'info>, bool, bool)> {
+    proof.iter().map(|ai| (ai, false, false)).collect()
+}
+
+impl<'a, 'info> TreeCpi<'a, 'info> {
+    /// MintV2 into the university's Core collection. `co_signers` are the authorities of
+    /// co-issuers who signed; Bubblegum verifies them as creators.
+    pub fn mint(
+        &self,
+        mpl_core_program: &'a AccountInfo<'info>,
+        mpl_core_cpi_signer: &'a AccountInfo<'info>,
+        leaf_owner: &'a AccountInfo<'info>,
+        metadata: MetadataArgsV2,
+        co_signers: &[(&'a AccountInfo<'info>, bool, bool)],
+    ) -> Result<()> {
+        MintV2CpiBuilder::new(self.bubblegum_program)
+            .tree_config(self.tree_config)
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(None)
+            .merkle_tree(self.merkle_tree)
+            .payer(self.authority)
+            .tree_creator_or_delegate(Some(self.authority))
+            .compression_program(self.compression_program)
+            .log_wrapper(self.log_wrapper)
+            .system_program(self.system_program)
+            .mpl_core_program(mpl_core_program)
+            .core_collection(Some(self.core_collection))
+            .mpl_core_cpi_signer(Some(mpl_core_cpi_signer))
+            .collection_authority(Some(self.authority))
+            .metadata(metadata)
+            .add_remaining_accounts(co_signers)
+            .invoke()?;
+        Ok(())
+    }
+
+    /// SetNonTransferableV2 (soulbound) on the leaf just minted from `metadata`.
+    /// The root and leaf hashes are computed on chain, not taken from the client.
+    pub fn set_non_transferable(
+        &self,
+        university_tree: &UniversityTree,
+        leaf_owner: &'a AccountInfo<'info>,
+        metadata: &MetadataArgsV2,
+        nonce: u64,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let leaf = LeafProof {
+            root: current_merkle_root(
+                self.merkle_tree,
+                university_tree.max_depth,
+                university_tree.max_buffer_size,
+            )?,
+            data_hash: leaf_data_hash(metadata)?,
+            creator_hash: hash_creators(&metadata.creators),
+            nonce,
+            index: nonce as u32,
+            asset_data_hash: None,
+            flags: None,
+        };
+        self.set_leaf_non_transferable(leaf_owner, &leaf, proof)
+    }
+
+    /// SetNonTransferableV2 (soulbound) on an existing leaf, signed by the collection's
+    /// PermanentFreezeDelegate.
+    pub fn set_leaf_non_transferable(
+        &self,
+        leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = SetNonTransferableV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .payer(self.authority)
+            .authority(Some(self.authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(Some(leaf_owner))
+            .merkle_tree(self.merkle_tree)
+            .core_collection(self.core_collection)
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .data_hash(leaf.data_hash)
+            .creator_hash(leaf.creator_hash)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke()?;
+        Ok(())
+    }
+
+    /// BurnV2 through the collection's PermanentBurnDelegate.
+    pub fn burn(
+        &self,
+        mpl_core_program: &'a AccountInfo<'info>,
+        mpl_core_cpi_signer: &'a AccountInfo<'info>,
+        leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = BurnV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .payer(self.authority)
+            .authority(Some(self.authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(None)
+            .merkle_tree(self.merkle_tree)
+            .core_collection(Some(self.core_collection))
+            .mpl_core_cpi_signer(Some(mpl_core_cpi_signer))
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .mpl_core_program(mpl_core_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .data_hash(leaf.data_hash)
+            .creator_hash(leaf.creator_hash)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke()?;
+        Ok(())
+    }
+
+    /// FreezeV2 through the collection's PermanentFreezeDelegate.
+    pub fn freeze(
+        &self,
+        leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = FreezeV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .payer(self.authority)
+            .authority(Some(self.authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(leaf_owner)
+            .merkle_tree(self.merkle_tree)
+            .core_collection(Some(self.core_collection))
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .data_hash(leaf.data_hash)
+            .creator_hash(leaf.creator_hash)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke()?;
+        Ok(())
+    }
+
+    /// ThawV2 through the collection's PermanentFreezeDelegate.
+    pub fn thaw(
+        &self,
+        leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = ThawV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .payer(self.authority)
+            .authority(Some(self.authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(leaf_owner)
+            .merkle_tree(self.merkle_tree)
+            .core_collection(Some(self.core_collection))
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .data_hash(leaf.data_hash)
+            .creator_hash(leaf.creator_hash)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke()?;
+        Ok(())
+    }
+
+    /// TransferV2 out of a claim escrow, signed by the escrow PDA (the current leaf owner).
+    pub fn transfer_from_escrow(
+        &self,
+        escrow: &'a AccountInfo<'info>,
+        escrow_seeds: &[&[u8]],
+        new_leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.transfer_signed(escrow, escrow_seeds, escrow, new_leaf_owner, leaf, proof)
+    }
+
+    /// TransferV2 signed by a program PDA `authority`: the leaf owner itself (claim escrow)
+    /// or the collection's PermanentTransferDelegate (recovery delegate).
+    pub fn transfer_signed(
+        &self,
+        authority: &'a AccountInfo<'info>,
+        authority_seeds: &[&[u8]],
+        leaf_owner: &'a AccountInfo<'info>,
+        new_leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = TransferV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .payer(self.authority)
+            .authority(Some(authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(Some(leaf_owner))
+            .new_leaf_owner(new_leaf_owner)
+            .merkle_tree(self.merkle_tree)
+            .core_collection(Some(self.core_collection))
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .data_hash(leaf.data_hash)
+            .creator_hash(leaf.creator_hash)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke_signed(&[authority_seeds])?;
+        Ok(())
+    }
+
+    /// UpdateMetadataV2 by the collection update authority. Bubblegum re-hashes
+    /// `current_metadata` against the leaf, so a wrong name/uri fails the proof.
+    pub fn update_metadata(
+        &self,
+        leaf_owner: &'a AccountInfo<'info>,
+        leaf: &LeafProof,
+        current_metadata: MetadataArgsV2,
+        update_args: UpdateArgs,
+        proof: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut cpi = UpdateMetadataV2CpiBuilder::new(self.bubblegum_program);
+        cpi.tree_config(self.tree_config)
+            .authority(Some(self.authority))
+            .leaf_owner(leaf_owner)
+            .leaf_delegate(Some(leaf_owner))
+            .payer(self.authority)
+            .merkle_tree(self.merkle_tree)
+            .core_collection(Some(self.core_collection))
+            .log_wrapper(self.log_wrapper)
+            .compression_program(self.compression_program)
+            .system_program(self.system_program)
+            .root(leaf.root)
+            .nonce(leaf.nonce)
+            .index(leaf.index)
+            .current_metadata(current_metadata)
+            .update_args(update_args)
+            .add_remaining_accounts(&proof_accounts(proof));
+        if let Some(asset_data_hash) = leaf.asset_data_hash {
+            cpi.asset_data_hash(asset_data_hash);
+        }
+        if let Some(flags) = leaf.flags {
+            cpi.flags(flags);
+        }
+        cpi.invoke()?;
+        Ok(())
+    }
+}
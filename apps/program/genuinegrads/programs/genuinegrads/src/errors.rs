@@ -30,6 +30,112 @@ pub enum GenuineGradsError {
     MissingRemainingAccounts,
     #[msg("Missing merkle proof accounts.")]
     MissingMerkleProof,
-    #[msg("Invalid burn reason.")]
+    #[msg("Invalid burn/revocation reason.")]
     InvalidBurnReason,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("No pending update to apply")]
+    NoPendingUpdate,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Asset id does not match the next leaf of the tree")]
+    AssetIdMismatch,
+    #[msg("Invalid Merkle tree account")]
+    InvalidMerkleTree,
+    #[msg("Certificate is already non-transferable")]
+    AlreadyNonTransferable,
+    #[msg("Certificate is already revoked")]
+    AlreadyRevoked,
+    #[msg("Certificate is not revoked")]
+    NotRevoked,
+    #[msg("Revocation list shard does not cover this leaf")]
+    RevocationShardMismatch,
+    #[msg("Reason code not permitted for this caller")]
+    ReasonNotPermittedForCaller,
+    #[msg("Certificate has been superseded by a reissue")]
+    CertificateSuperseded,
+    #[msg("Certificate metadata is immutable")]
+    CertificateImmutable,
+    #[msg("Current metadata does not match the certificate record")]
+    MetadataMismatch,
+    #[msg("Invalid certificate validity window")]
+    InvalidValidityWindow,
+    #[msg("Certificate is not valid yet")]
+    CertificateNotYetValid,
+    #[msg("Certificate has expired")]
+    CertificateExpired,
+    #[msg("Claimable certificates need a future expiry and the escrow PDA as recipient")]
+    InvalidClaimSetup,
+    #[msg("Claim is not signed by the claim key for this claimant")]
+    InvalidClaimSignature,
+    #[msg("Claim window has expired")]
+    ClaimExpired,
+    #[msg("Claim window has not expired yet")]
+    ClaimNotExpired,
+    #[msg("Recovery target must differ from the current owner")]
+    InvalidRecoveryTarget,
+    #[msg("Student consent is required: recipient must co-sign or give a MintConsent")]
+    StudentConsentRequired,
+    #[msg("Mint consent has already been used")]
+    ConsentAlreadyUsed,
+    #[msg("Too many co-issuers")]
+    TooManyCoIssuers,
+    #[msg("Invalid co-issuer")]
+    InvalidCoIssuer,
+    #[msg("Co-issuer neither signed nor approved this certificate")]
+    CoIssuanceNotApproved,
+    #[msg("Invalid collection issuance policy")]
+    InvalidIssuancePolicy,
+    #[msg("Certificate name does not follow the collection policy")]
+    NameNotAllowedByPolicy,
+    #[msg("Metadata URI scheme is not allowed")]
+    UriSchemeNotAllowed,
+    #[msg("Metadata hash is required")]
+    MissingMetadataHash,
+    #[msg("Invalid document digests")]
+    InvalidDocumentDigest,
+    #[msg("Batch must contain between 1 and MAX_BATCH_MINT entries")]
+    InvalidBatchSize,
+    #[msg("Credential id is zero or its mapping account is missing")]
+    InvalidCredentialId,
+    #[msg("Credential id has already been minted")]
+    DuplicateCredentialId,
+    #[msg("Invalid cohort parameters")]
+    InvalidCohort,
+    #[msg("Cohort is sealed")]
+    CohortSealed,
+    #[msg("Cohort account does not match the certificate")]
+    CohortMismatch,
+    #[msg("Cohort is full")]
+    CohortFull,
+    #[msg("Invalid credential batch: empty, zero root or bad schema id")]
+    InvalidCredentialBatch,
+    #[msg("Public input is not a BN254 scalar field element")]
+    InvalidPublicInput,
+    #[msg("Zero-knowledge proof verification failed")]
+    InvalidProof,
+    #[msg("Invalid verifying key: bad circuit id, input count, chunk or hash")]
+    InvalidVerifyingKey,
+    #[msg("Verifying key is finalized and can no longer be edited")]
+    VerifyingKeyFinalized,
+    #[msg("Verifying key has not been finalized")]
+    VerifyingKeyNotFinalized,
+    #[msg("Verifying key is already deprecated")]
+    VerifyingKeyDeprecated,
+    #[msg("Proof inputs do not match the registered commitment's certificate or achievement")]
+    CommitmentMismatch,
+    #[msg("Proof request mismatch: missing request or nullifier, wrong verifier or achievement, or bad expiry")]
+    InvalidProofRequest,
+    #[msg("Proof request has expired")]
+    ProofRequestExpired,
+    #[msg("Certificate record account is not the record PDA of this leaf")]
+    CertificateRecordMismatch,
+    #[msg("Certificate is still held in claim escrow")]
+    CertificateInEscrow,
+    #[msg("Revocation note is empty or longer than 120 characters")]
+    InvalidRevocationNote,
+    #[msg("University authority must co-sign to lock the certificate on claim")]
+    MissingUniversityAuthority,
+    #[msg("Collection has no PermanentTransferDelegate; it can only be added at collection creation")]
+    RecoveryDelegateUnsupported,
 }
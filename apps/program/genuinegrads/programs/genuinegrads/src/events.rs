@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::states::{DocumentDigest, ExternalProgramIds, ReissueMode, RevocationReason};
 
 #[event]
 pub struct ConfigInitialized {
@@ -67,9 +68,366 @@ pub struct CertificateMintedV2 {
     pub name: String,
     pub uri: String,
     pub attached_collection: bool,
+    pub asset_id: Pubkey,
+    pub non_transferable: bool,
+    /// Partner universities of a joint degree
+    pub co_issuers: Vec<Pubkey>,
+    /// SHA-256 of the off-chain metadata JSON
+    pub metadata_hash: [u8; 32],
+    /// Anchored document digests (PDF diploma, transcript, ...)
+    pub documents: Vec<DocumentDigest>,
+    /// sha256 of the backend credential id (idempotency key), if any
+    pub credential_id: Option<[u8; 32]>,
 }
 
+#[event]
+pub struct CertificateLockedV2 {
+    pub admin: Pubkey,
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub collection: Pubkey,
+    pub asset_id: Pubkey,
+    pub nonce: u64,
+    pub locked_at: i64,
+}
+
+#[event]
+pub struct ProgramAllowlistInitialized {
+    pub admin: Pubkey,
+    pub program_allowlist: Pubkey,
+    pub version: u32,
+    pub programs: ExternalProgramIds,
+}
+
+#[event]
+pub struct ProgramAllowlistUpdateProposed {
+    pub admin: Pubkey,
+    pub program_allowlist: Pubkey,
+    pub current_version: u32,
+    pub programs: ExternalProgramIds,
+    pub eta: i64,
+}
 
+#[event]
+pub struct ProgramAllowlistUpdated {
+    pub admin: Pubkey,
+    pub program_allowlist: Pubkey,
+    pub version: u32,
+    pub programs: ExternalProgramIds,
+}
+
+#[event]
+pub struct CertificateRevoked {
+    pub admin: Pubkey,
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub collection: Pubkey,
+    pub asset_id: Pubkey,
+    pub nonce: u64,
+    pub reason: RevocationReason,
+    pub note: Option<String>,
+    pub case_reference_hash: Option<[u8; 32]>,
+    pub revoked_at: i64,
+}
 
+#[event]
+pub struct CertificateReinstated {
+    pub admin: Pubkey,
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub leaf_owner: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub collection: Pubkey,
+    pub asset_id: Pubkey,
+    pub nonce: u64,
+    pub previous_reason: Option<RevocationReason>,
+    pub reinstated_at: i64,
+}
 
+#[event]
+pub struct RevocationListCreated {
+    pub university: Pubkey,
+    pub university_tree: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub revocation_list: Pubkey,
+    pub shard_index: u32,
+}
 
+#[event]
+pub struct CertificateReissued {
+    pub admin: Pubkey,
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub old_asset_id: Pubkey,
+    pub new_asset_id: Pubkey,
+    pub mode: ReissueMode,
+    pub note: Option<String>,
+    pub reissued_at: i64,
+}
+
+#[event]
+pub struct CollectionPolicyUpdated {
+    pub university: Pubkey,
+    pub collection: Pubkey,
+    pub mutable_metadata: bool,
+    pub symbol: String,
+    pub seller_fee_basis_points: u16,
+    pub name_prefix: String,
+    pub name_max_len: u8,
+    pub allowed_uri_schemes: u8,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct CertificateMetadataUpdated {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub asset_id: Pubkey,
+    pub name: String,
+    pub uri: String,
+    /// name_uri_hash of the replaced metadata
+    pub previous_name_uri_hash: [u8; 32],
+    /// SHA-256 of the new metadata JSON
+    pub metadata_hash: [u8; 32],
+    pub metadata_chain: [u8; 32],
+    pub metadata_version: u32,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct CertificateRenewed {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub asset_id: Pubkey,
+    pub previous_valid_until: i64,
+    pub valid_until: Option<i64>,
+    pub renewal_count: u32,
+    pub renewed_at: i64,
+}
+
+#[event]
+pub struct CertificateClaimed {
+    pub university: Pubkey,
+    pub asset_id: Pubkey,
+    pub escrow: Pubkey,
+    pub claimant: Pubkey,
+    pub claimed_at: i64,
+    pub non_transferable: bool,
+}
+
+#[event]
+pub struct CertificateReclaimed {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub asset_id: Pubkey,
+    pub escrow: Pubkey,
+    pub reclaimed_at: i64,
+}
+
+#[event]
+pub struct CertificateRecoveryRequested {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub asset_id: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct CertificateRecoveryCancelled {
+    pub university: Pubkey,
+    pub asset_id: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct CertificateRecovered {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub asset_id: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub recovered_at: i64,
+}
+
+#[event]
+pub struct StudentConsentPolicyUpdated {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub require_student_consent: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct MintConsentGiven {
+    pub university: Pubkey,
+    pub student: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub given_at: i64,
+}
+
+#[event]
+pub struct MintConsentWithdrawn {
+    pub university: Pubkey,
+    pub student: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct CoIssuanceApproved {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub partner: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub approved_at: i64,
+}
+
+#[event]
+pub struct AllowedUriSchemesUpdated {
+    pub owner: Pubkey,
+    pub allowed_uri_schemes: u8,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct CertificateBatchMinted {
+    pub university: Pubkey,
+    pub authority: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub batch_id: [u8; 32],
+    pub chunk_index: u32,
+    pub first_nonce: u64,
+    pub count: u32,
+    pub minted_at: i64,
+}
+
+#[event]
+pub struct CohortCreated {
+    pub university: Pubkey,
+    pub cohort: Pubkey,
+    pub programme: String,
+    pub year: u16,
+    pub conferral_date: i64,
+    pub expected_count: u32,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct CohortSealed {
+    pub university: Pubkey,
+    pub cohort: Pubkey,
+    /// Merkle root over the cohort's asset ids (leaf = sha256(asset_id))
+    pub root: [u8; 32],
+    pub issued_count: u32,
+    pub revoked_count: u32,
+    pub expected_count: u32,
+    pub sealed_at: i64,
+}
+
+#[event]
+pub struct CredentialBatchAnchored {
+    pub university: Pubkey,
+    pub credential_batch: Pubkey,
+    pub batch_id: [u8; 32],
+    pub root: [u8; 32],
+    pub count: u32,
+    pub schema_id: String,
+    pub anchored_at: i64,
+}
+
+#[event]
+pub struct CredentialBatchRevoked {
+    pub university: Pubkey,
+    pub credential_batch: Pubkey,
+    pub batch_id: [u8; 32],
+    pub reason: RevocationReason,
+    pub note: Option<String>,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct ZkProofVerified {
+    pub verifier: Pubkey,
+    pub verifying_key: Pubkey,
+    /// Super admin that published the key; verifiers should check it is the one they trust
+    pub admin: Pubkey,
+    pub university: Pubkey,
+    pub asset_id: Pubkey,
+    pub circuit_id: String,
+    pub version: u16,
+    pub key_deprecated: bool,
+    pub commitment: [u8; 32],
+    pub credential_hash: [u8; 32],
+    pub achievement_hash: [u8; 32],
+    /// Request the proof answered and the nullifier it spent
+    pub proof_request: Pubkey,
+    pub nullifier: [u8; 32],
+    pub verified_at: i64,
+}
+
+#[event]
+pub struct VerifyingKeyCreated {
+    pub admin: Pubkey,
+    pub verifying_key: Pubkey,
+    pub circuit_id: String,
+    pub version: u16,
+    pub public_inputs: u8,
+    pub key_hash: [u8; 32],
+    pub created_at: i64,
+}
+
+#[event]
+pub struct VerifyingKeyFinalized {
+    pub admin: Pubkey,
+    pub verifying_key: Pubkey,
+    pub circuit_id: String,
+    pub version: u16,
+    pub key_hash: [u8; 32],
+    pub finalized_at: i64,
+}
+
+#[event]
+pub struct VerifyingKeyDeprecated {
+    pub admin: Pubkey,
+    pub verifying_key: Pubkey,
+    pub circuit_id: String,
+    pub version: u16,
+    pub deprecated_at: i64,
+}
+
+#[event]
+pub struct AchievementCommitmentRegistered {
+    pub university: Pubkey,
+    pub asset_id: Pubkey,
+    pub achievement_commitment: Pubkey,
+    pub commitment: [u8; 32],
+    pub achievement_hash: [u8; 32],
+    pub registered_at: i64,
+}
+
+#[event]
+pub struct ProofRequestCreated {
+    pub verifier: Pubkey,
+    pub proof_request: Pubkey,
+    pub context: [u8; 32],
+    pub achievement_hash: Option<[u8; 32]>,
+    pub expires_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct RecoveryDelegateMigrated {
+    pub university: Pubkey,
+    pub collection: Pubkey,
+    pub recovery_delegate: Pubkey,
+    pub migrated_at: i64,
+}
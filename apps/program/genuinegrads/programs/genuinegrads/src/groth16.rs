@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use solana_bn254::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+use crate::errors::GenuineGradsError;
+
+/// BN254 base field modulus q, big-endian.
+pub const BN254_BASE_FIELD: [u8; 32] = [
+    48, 100, 78, 114, 225, 49, 160, 41, 184, 80, 69, 182, 129, 129, 88, 93,
+    151, 129, 106, 145, 104, 113, 202, 141, 60, 32, 140, 22, 216, 124, 253, 71,
+];
+
+/// BN254 scalar field modulus r, big-endian. Public inputs must be below it.
+pub const BN254_SCALAR_FIELD: [u8; 32] = [
+    48, 100, 78, 114, 225, 49, 160, 41, 184, 80, 69, 182, 129, 129, 88, 93,
+    40, 51, 232, 72, 121, 185, 112, 145, 67, 225, 245, 147, 240, 0, 0, 1,
+];
+
+pub const G1_LEN: usize = 64;
+pub const G2_LEN: usize = 128;
+
+/// alpha_g1 || beta_g2 || gamma_g2 || delta_g2, ahead of the IC points.
+pub const VK_HEADER_LEN: usize = G1_LEN + 3 * G2_LEN;
+
+/// Groth16 verifying key in the alt_bn128 syscall encoding: big-endian coordinates,
+/// G2 points as x_c1 || x_c0 || y_c1 || y_c0 (snarkjs exports them c0 first).
+pub struct Groth16VerifyingKey<'a> {
+    pub alpha_g1: &'a [u8],
+    pub beta_g2: &'a [u8],
+    pub gamma_g2: &'a [u8],
+    pub delta_g2: &'a [u8],
+    /// One G1 point per public input, plus the constant term first
+    pub ic: &'a [u8],
+}
+
+impl<'a> Groth16VerifyingKey<'a> {
+    /// Splits `alpha_g1 || beta_g2 || gamma_g2 || delta_g2 || ic[..]`.
+    pub fn from_bytes(data: &'a [u8]) -> Option<Self> {
+        if data.len() < VK_HEADER_LEN + G1_LEN || (data.len() - VK_HEADER_LEN) % G1_LEN != 0 {
+            return None;
+        }
+        let (alpha_g1, rest) = data.split_at(G1_LEN);
+        let (beta_g2, rest) = rest.split_at(G2_LEN);
+        let (gamma_g2, rest) = rest.split_at(G2_LEN);
+        let (delta_g2, ic) = rest.split_at(G2_LEN);
+        Some(Self { alpha_g1, beta_g2, gamma_g2, delta_g2, ic })
+    }
+
+    pub fn public_inputs(&self) -> usize {
+        self.ic.len() / G1_LEN - 1
+    }
+}
+
+/// Whether a big-endian 32-byte value is a canonical scalar field element.
+pub fn is_field_element(value: &[u8; 32]) -> bool {
+    value < &BN254_SCALAR_FIELD
+}
+
+/// Reduces a big-endian 256-bit value mod r, matching `stringToFieldElement`
+/// (sha256 mod r) in the frontend and backend.
+pub fn to_field_element(value: [u8; 32]) -> [u8; 32] {
+    let mut out = value;
+    while !is_field_element(&out) {
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = out[i] as i16 - BN254_SCALAR_FIELD[i] as i16 - borrow;
+            borrow = (diff < 0) as i16;
+            out[i] = diff.rem_euclid(256) as u8;
+        }
+    }
+    out
+}
+
+/// Negates a G1 point (x, q - y); the identity stays as is.
+fn negate_g1(point: &[u8; G1_LEN]) -> [u8; G1_LEN] {
+    let mut out = *point;
+    if point.iter().all(|b| *b == 0) {
+        return out;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = BN254_BASE_FIELD[i] as i16 - point[32 + i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        out[32 + i] = diff.rem_euclid(256) as u8;
+    }
+    out
+}
+
+/// Checks a Groth16 proof as produced by snarkjs (proof_a not negated):
+/// e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1,
+/// with vk_x = IC[0] + sum(input_i * IC[i + 1]).
+/// Malformed points surface as `InvalidProof`, out-of-range inputs as `InvalidPublicInput`.
+pub fn verify_groth16(
+    vk: &Groth16VerifyingKey,
+    proof_a: &[u8; 64],
+    proof_b: &[u8; 128],
+    proof_c: &[u8; 64],
+    public_inputs: &[[u8; 32]],
+) -> Result<bool> {
+    require!(vk.public_inputs() == public_inputs.len(), GenuineGradsError::InvalidPublicInput);
+
+    let mut ic = vk.ic.chunks_exact(G1_LEN);
+    let mut vk_x = ic.next().unwrap_or_default().to_vec();
+    for (input, point) in public_inputs.iter().zip(ic) {
+        require!(is_field_element(input), GenuineGradsError::InvalidPublicInput);
+        let product = alt_bn128_multiplication(&[point, input.as_slice()].concat())
+            .map_err(|_| GenuineGradsError::InvalidProof)?;
+        vk_x = alt_bn128_addition(&[vk_x.as_slice(), product.as_slice()].concat())
+            .map_err(|_| GenuineGradsError::InvalidProof)?;
+    }
+
+    let pairing_input = [
+        negate_g1(proof_a).as_slice(),
+        proof_b.as_slice(),
+        vk.alpha_g1,
+        vk.beta_g2,
+        vk_x.as_slice(),
+        vk.gamma_g2,
+        proof_c.as_slice(),
+        vk.delta_g2,
+    ]
+    .concat();
+    let result = alt_bn128_pairing(&pairing_input).map_err(|_| GenuineGradsError::InvalidProof)?;
+
+    Ok(result.len() == 32 && result[..31].iter().all(|b| *b == 0) && result[31] == 1)
+}
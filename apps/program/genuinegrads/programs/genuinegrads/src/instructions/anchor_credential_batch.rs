@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CredentialBatch, GlobalConfig, University, CREDENTIAL_BATCH_SEED, GLOBAL_CONFIG_SEED,
+    MAX_SCHEMA_ID_LEN, UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CredentialBatchAnchored;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AnchorCredentialBatchArgs {
+    /// Backend batch id (sha256 of the job id)
+    pub batch_id: [u8; 32],
+    /// Merkle root over the credential leaf hashes
+    pub root: [u8; 32],
+    /// Number of credentials committed by the root
+    pub count: u32,
+    /// Off-chain credential schema (at most MAX_SCHEMA_ID_LEN bytes)
+    pub schema_id: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: AnchorCredentialBatchArgs)]
+pub struct AnchorCredentialBatch<'info> {
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["credential_batch", university, batch_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CredentialBatch::INIT_SPACE,
+        seeds = [CREDENTIAL_BATCH_SEED, university.key().as_ref(), args.batch_id.as_ref()],
+        bump
+    )]
+    pub credential_batch: Account<'info, CredentialBatch>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AnchorCredentialBatch>, args: AnchorCredentialBatchArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require!(
+        args.count > 0 && args.root != [0u8; 32],
+        GenuineGradsError::InvalidCredentialBatch
+    );
+    require!(
+        !args.schema_id.is_empty() && args.schema_id.len() <= MAX_SCHEMA_ID_LEN,
+        GenuineGradsError::InvalidCredentialBatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let batch = &mut ctx.accounts.credential_batch;
+    batch.university = ctx.accounts.university.key();
+    batch.batch_id = args.batch_id;
+    batch.root = args.root;
+    batch.count = args.count;
+    batch.schema_id = args.schema_id;
+    batch.anchored_at = now;
+    batch.revoked = false;
+    batch.revocation_reason = None;
+    batch.revocation_note = String::new();
+    batch.revoked_at = 0;
+    batch.bump = ctx.bumps.credential_batch;
+
+    emit!(CredentialBatchAnchored {
+        university: batch.university,
+        credential_batch: batch.key(),
+        batch_id: batch.batch_id,
+        root: batch.root,
+        count: batch.count,
+        schema_id: batch.schema_id.clone(),
+        anchored_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::states::{GlobalConfig, ProgramAllowlist, GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::ProgramAllowlistUpdated;
+
+#[derive(Accounts)]
+pub struct ApplyProgramAllowlistUpdate<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        mut,
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+}
+
+pub fn handler(ctx: Context<ApplyProgramAllowlistUpdate>) -> Result<()> {
+    let gc = &ctx.accounts.global_config;
+    require_keys_eq!(ctx.accounts.super_admin.key(), gc.owner, GenuineGradsError::Unauthorized);
+
+    let now = Clock::get()?.unix_timestamp;
+    let list = &mut ctx.accounts.program_allowlist;
+
+    let pending = list.pending.ok_or(GenuineGradsError::NoPendingUpdate)?;
+    require!(now >= list.pending_eta, GenuineGradsError::TimelockNotElapsed);
+
+    list.programs = pending;
+    list.pending = None;
+    list.pending_eta = 0;
+    list.version = list.version.checked_add(1).ok_or(GenuineGradsError::MathOverflow)?;
+    list.updated_at = now;
+
+    emit!(ProgramAllowlistUpdated {
+        admin: list.admin,
+        program_allowlist: list.key(),
+        version: list.version,
+        programs: list.programs,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CoIssuanceApproval, GlobalConfig, University, CO_ISSUANCE_SEED, GLOBAL_CONFIG_SEED,
+    UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CoIssuanceApproved;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ApproveCoIssuanceArgs {
+    /// name_uri_hash(name, uri) of the joint-degree certificate
+    pub credential_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: ApproveCoIssuanceArgs)]
+pub struct ApproveCoIssuance<'info> {
+    /// Co-issuing university's authority is also the payer
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Co-issuing university; must be ACTIVE
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// University that will mint the joint degree
+    #[account(
+        constraint = partner.key() != university.key() @ GenuineGradsError::InvalidCoIssuer,
+        constraint = partner.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub partner: Account<'info, University>,
+
+    /// PDA = ["co_issuance", university, partner, credential_hash]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CoIssuanceApproval::INIT_SPACE,
+        seeds = [CO_ISSUANCE_SEED, university.key().as_ref(), partner.key().as_ref(), args.credential_hash.as_ref()],
+        bump
+    )]
+    pub co_issuance_approval: Account<'info, CoIssuanceApproval>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApproveCoIssuance>, args: ApproveCoIssuanceArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let now = Clock::get()?.unix_timestamp;
+    let approval = &mut ctx.accounts.co_issuance_approval;
+    approval.university = ctx.accounts.university.key();
+    approval.partner = ctx.accounts.partner.key();
+    approval.credential_hash = args.credential_hash;
+    approval.asset_id = None;
+    approval.created_at = now;
+    approval.bump = ctx.bumps.co_issuance_approval;
+
+    emit!(CoIssuanceApproved {
+        university: approval.university,
+        authority: ctx.accounts.university_authority.key(),
+        partner: approval.partner,
+        credential_hash: approval.credential_hash,
+        approved_at: now,
+    });
+
+    Ok(())
+}
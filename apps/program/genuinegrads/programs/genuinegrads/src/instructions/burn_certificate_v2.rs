@@ -4,22 +4,20 @@ use anchor_lang::prelude::*;
 
 use crate::errors::GenuineGradsError;
 use crate::states::{
-    GlobalConfig, University, UniversityCollection, UniversityTree,
-    GLOBAL_CONFIG_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+    CertificateRecord, CertificateStatus, Cohort, GlobalConfig, ProgramAllowlist, RevocationList,
+    RevocationReason, University, UniversityCollection, UniversityTree, check_record_cohort,
+    validate_revocation_note, GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED,
+    UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
 };
 
 // Bubblegum v2 CPI
-use mpl_bubblegum::ID as BUBBLEGUM_ID;
-use mpl_bubblegum::instructions::{BurnV2Cpi, BurnV2CpiAccounts, BurnV2InstructionArgs};
+use crate::bubblegum::{LeafProof, TreeCpi};
 
-// SPL-Compression + Noop
-use crate::utils::{Noop, SplAccountCompression};
-
-// MPL Core (for Core collection + CPI signer)
-use mpl_core::ID as MPL_CORE_ID;
+// Bubblegum's MPL Core CPI signer
+use crate::utils::{asset_id, mpl_core_cpi_signer};
 
 /// Event: emitted after a successful burn.
-/// This is your audit trail + includes a reason string.
+/// This is your audit trail + includes the reason code.
 #[event]
 pub struct CertificateBurnedV2 {
     pub admin: Pubkey,
@@ -31,10 +29,13 @@ pub struct CertificateBurnedV2 {
     pub tree_config: Pubkey,
     pub collection: Pubkey,
 
+    pub asset_id: Pubkey,
     pub index: u32,
     pub nonce: u64,
 
-    pub reason: String,
+    pub reason: RevocationReason,
+    pub note: Option<String>,
+    pub case_reference_hash: Option<[u8; 32]>,
     pub burned_at: i64,
 }
 
@@ -51,8 +52,12 @@ pub struct BurnCertificateArgs {
     pub asset_data_hash: Option<[u8; 32]>,
     pub flags: Option<u8>,
 
-    /// Human readable reason for burning/revoking
-    pub reason: String,
+    /// Why the certificate is burned
+    pub reason: RevocationReason,
+    /// Optional free-text context (max 120 chars)
+    pub note: Option<String>,
+    /// Optional hash of the off-chain case file
+    pub case_reference_hash: Option<[u8; 32]>,
 }
 
 #[derive(Accounts)]
@@ -61,12 +66,24 @@ pub struct BurnCertificateV2<'info> {
     #[account(mut)]
     pub university_authority: Signer<'info>,
 
+    /// Super admin co-signer, required for `LegalOrder` / `IssuerClosure`
+    #[account(address = global_config.owner @ GenuineGradsError::Unauthorized)]
+    pub super_admin: Option<Signer<'info>>,
+
     #[account(
         seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
         bump = global_config.bump
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
     /// University must be ACTIVE, tied to this authority & admin
     /// PDA = ["university", university_authority]
     #[account(
@@ -106,25 +123,45 @@ pub struct BurnCertificateV2<'info> {
     #[account(mut)]
     pub core_collection: UncheckedAccount<'info>,
 
-    /// CHECK: MPL Core program id
-    #[account(address = MPL_CORE_ID)]
+    /// CHECK: MPL Core program, ID checked against the allowlist
+    #[account(executable)]
     pub mpl_core_program: UncheckedAccount<'info>,
 
-    /// CHECK: MPL Core CPI signer PDA (you validate like in mint)
+    /// CHECK: Bubblegum's MPL Core CPI signer PDA (validated like in mint)
     pub mpl_core_cpi_signer: UncheckedAccount<'info>,
 
-    /// CHECK: leaf owner (student wallet) – does NOT need to sign if PermanentBurnDelegate exists
+    /// Revocation bitmap shard covering this leaf (burned leaves stay marked here)
+    /// PDA = ["revocation_list", university_tree, nonce / 16384]
+    #[account(
+        mut,
+        constraint = revocation_list.load()?.university_tree == university_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+
+    /// CHECK: certificate record PDA = ["certificate", asset_id]; always required.
+    /// Must hold the leaf's record, or be empty for legacy leaves minted before records.
+    #[account(mut)]
+    pub certificate_record: UncheckedAccount<'info>,
+
+    /// Cohort named by the certificate record; required when it has one
+    #[account(mut)]
+    pub cohort: Option<Account<'info, Cohort>>,
+
+    /// CHECK: leaf owner (student wallet) – does NOT need to sign if PermanentBurnDelegate exists.
+    /// Must sign only when the reason is `StudentRequest`.
     pub leaf_owner: UncheckedAccount<'info>,
 
-    /// Bubblegum program
-    /// CHECK: ID verified in handler
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
     pub bubblegum_program: UncheckedAccount<'info>,
 
-    /// SPL Account Compression
-    pub compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
 
-    /// SPL Noop log wrapper
-    pub log_wrapper: Program<'info, Noop>,
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -134,33 +171,39 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BurnCertificateV2<'info>>,
     require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
     require!(ctx.accounts.university.is_active, GenuineGradsError::UniversityInactive);
 
-    // Reason guard (keep event size reasonable)
-    // (Tune these limits if you want.)
-    require!(
-        !args.reason.trim().is_empty() && args.reason.len() <= 120,
-        GenuineGradsError::InvalidBurnReason
-    );
+    // --- Reason guards ---
+    args.reason.validate_caller(
+        ctx.accounts.leaf_owner.is_signer,
+        ctx.accounts.super_admin.is_some(),
+    )?;
+    validate_revocation_note(&args.note)?;
 
-    // Program IDs (same as mint)
+    // Program IDs (allowlisted in ProgramAllowlist, same as mint)
+    let programs = ctx.accounts.program_allowlist.programs;
     require_keys_eq!(
         ctx.accounts.bubblegum_program.key(),
-        BUBBLEGUM_ID,
+        programs.bubblegum,
+        GenuineGradsError::InvalidProgramExecutable
+    );
+    require_keys_eq!(
+        ctx.accounts.mpl_core_program.key(),
+        programs.mpl_core,
         GenuineGradsError::InvalidProgramExecutable
     );
     require_keys_eq!(
         ctx.accounts.compression_program.key(),
-        SplAccountCompression::id(),
+        programs.account_compression,
         GenuineGradsError::InvalidProgramExecutable
     );
     require_keys_eq!(
         ctx.accounts.log_wrapper.key(),
-        Noop::id(),
+        programs.noop,
         GenuineGradsError::InvalidProgramExecutable
     );
 
     // Verify tree_config PDA belongs to this merkle_tree (same style as your mint)
     let (expected_tree_config, _) =
-        Pubkey::find_program_address(&[ctx.accounts.merkle_tree.key.as_ref()], &BUBBLEGUM_ID);
+        Pubkey::find_program_address(&[ctx.accounts.merkle_tree.key.as_ref()], &programs.bubblegum);
     require_keys_eq!(
         ctx.accounts.tree_config.key(),
         expected_tree_config,
@@ -186,14 +229,34 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BurnCertificateV2<'info>>,
         GenuineGradsError::CollectionMismatch
     );
 
-    // Same style you used in mint (hardcoded signer)
-    // If you want, we can replace this with proper PDA derivation later.
+    // Bubblegum's MPL Core CPI signer (same derivation as in mint)
     require_keys_eq!(
         ctx.accounts.mpl_core_cpi_signer.key(),
-        pubkey!("CbNY3JiXdXNE9tPNEk1aRZVEkWdj2v7kfJLNQwZZgpXk"),
+        mpl_core_cpi_signer(&programs.bubblegum),
         GenuineGradsError::InvalidCoreCpiSigner
     );
 
+    // Record must track this leaf and not already be burned; only legacy leaves have none
+    let asset_id = asset_id(&programs.bubblegum, &ctx.accounts.merkle_tree.key(), args.nonce);
+    let record_info = ctx.accounts.certificate_record.to_account_info();
+    let mut record = CertificateRecord::load_for_asset(&record_info, &asset_id)?;
+    if let Some(rec) = &record {
+        require_keys_eq!(rec.university, ctx.accounts.university.key(), GenuineGradsError::Unauthorized);
+        require_keys_eq!(rec.merkle_tree, ctx.accounts.merkle_tree.key(), GenuineGradsError::TreeMismatch);
+        require!(args.nonce == rec.nonce && args.index as u64 == rec.nonce, GenuineGradsError::AssetIdMismatch);
+        require!(rec.status != CertificateStatus::Burned, GenuineGradsError::AlreadyRevoked);
+    }
+    check_record_cohort(
+        record.as_ref().and_then(|rec| rec.cohort),
+        ctx.accounts.cohort.as_ref(),
+    )?;
+
+    // Revocation shard must cover this leaf
+    require!(
+        ctx.accounts.revocation_list.load()?.shard_index == RevocationList::shard_for(args.nonce),
+        GenuineGradsError::RevocationShardMismatch
+    );
+
     // Proof path must be provided as remaining accounts
     require!(
         !ctx.remaining_accounts.is_empty(),
@@ -201,54 +264,55 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BurnCertificateV2<'info>>,
     );
 
     // --- Burn V2 CPI ---
-    // Bind account infos to extend their lifetime
-    let authority_info = ctx.accounts.university_authority.to_account_info();
-    let core_collection_info = ctx.accounts.core_collection.to_account_info();
-    let mpl_core_cpi_signer_info = ctx.accounts.mpl_core_cpi_signer.to_account_info();
-
-    // Build the CPI accounts struct
-    let cpi_accounts = BurnV2CpiAccounts {
-        tree_config: &ctx.accounts.tree_config,
-        payer: &ctx.accounts.university_authority,
-        authority: Some(&authority_info),
-        leaf_owner: &ctx.accounts.leaf_owner,
-        leaf_delegate: None,
-        merkle_tree: &ctx.accounts.merkle_tree,
-        core_collection: Some(&core_collection_info),
-        mpl_core_cpi_signer: Some(&mpl_core_cpi_signer_info),
-        log_wrapper: &ctx.accounts.log_wrapper,
-        compression_program: &ctx.accounts.compression_program,
-        mpl_core_program: &ctx.accounts.mpl_core_program,
-        system_program: &ctx.accounts.system_program,
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
     };
-
-    // Build instruction args
-    let cpi_args = BurnV2InstructionArgs {
+    let leaf = LeafProof {
         root: args.root,
         data_hash: args.data_hash,
         creator_hash: args.creator_hash,
-        asset_data_hash: args.asset_data_hash,
-        flags: args.flags,
         nonce: args.nonce,
         index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
     };
-
-    // Build the CPI instruction
-    let cpi = BurnV2Cpi::new(&ctx.accounts.bubblegum_program, cpi_accounts, cpi_args);
-
-    // Collect proof accounts as tuples (account_info, is_writable, is_signer)
-    let proof_accounts: Vec<_> = ctx
-        .remaining_accounts
-        .iter()
-        .map(|ai| (ai, false, false))
-        .collect();
-
-    // Invoke with remaining accounts for merkle proof
-    cpi.invoke_with_remaining_accounts(&proof_accounts)?;
+    tree.burn(
+        &ctx.accounts.mpl_core_program.to_account_info(),
+        &ctx.accounts.mpl_core_cpi_signer.to_account_info(),
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
 
     // Emit event (audit trail)
     let now = Clock::get()?.unix_timestamp;
 
+    // Burned leaves disappear from DAS; keep them discoverable in the bitmap
+    let mut list = ctx.accounts.revocation_list.load_mut()?;
+    list.set_revoked(args.nonce, true);
+    list.updated_at = now;
+    drop(list);
+
+    if let Some(rec) = &mut record {
+        // Revoked certificates are already counted in their cohort
+        if let (CertificateStatus::Active, Some(cohort)) = (rec.status, &mut ctx.accounts.cohort) {
+            cohort.revoked_count = cohort.revoked_count.checked_add(1).ok_or(GenuineGradsError::MathOverflow)?;
+        }
+        rec.status = CertificateStatus::Burned;
+        rec.revocation_reason = Some(args.reason);
+        rec.revocation_note = args.note.clone().unwrap_or_default();
+        rec.case_reference_hash = args.case_reference_hash;
+        rec.revoked_at = now;
+        rec.store(&record_info)?;
+    }
+
     emit!(CertificateBurnedV2 {
         admin: ctx.accounts.global_config.owner,
         university: ctx.accounts.university.key(),
@@ -257,9 +321,12 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BurnCertificateV2<'info>>,
         merkle_tree: ctx.accounts.merkle_tree.key(),
         tree_config: ctx.accounts.tree_config.key(),
         collection: ctx.accounts.core_collection.key(),
+        asset_id,
         index: args.index,
         nonce: args.nonce,
         reason: args.reason,
+        note: args.note,
+        case_reference_hash: args.case_reference_hash,
         burned_at: now,
     });
 
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::states::{RecoveryRequest, RECOVERY_REQUEST_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateRecoveryCancelled;
+
+#[derive(Accounts)]
+pub struct CancelCertificateRecovery<'info> {
+    /// The certificate's current owner (blocking a fraudulent request)
+    /// or the university authority that opened it
+    pub canceller: Signer<'info>,
+
+    /// PDA = ["recovery", asset_id]
+    #[account(
+        mut,
+        seeds = [RECOVERY_REQUEST_SEED, recovery_request.asset_id.as_ref()],
+        bump = recovery_request.bump,
+        constraint = canceller.key() == recovery_request.old_owner
+            || canceller.key() == recovery_request.authority @ GenuineGradsError::Unauthorized,
+        close = rent_receiver
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: receives the request's rent
+    #[account(mut, address = recovery_request.authority @ GenuineGradsError::Unauthorized)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelCertificateRecovery>) -> Result<()> {
+    let req = &ctx.accounts.recovery_request;
+
+    emit!(CertificateRecoveryCancelled {
+        university: req.university,
+        asset_id: req.asset_id,
+        cancelled_by: ctx.accounts.canceller.key(),
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
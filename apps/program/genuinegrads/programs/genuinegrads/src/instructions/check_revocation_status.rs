@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::states::RevocationList;
+use crate::errors::GenuineGradsError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CheckRevocationStatusArgs {
+    /// Leaf index (== nonce) of the certificate
+    pub leaf_index: u64,
+}
+
+/// Read-only status check; callable by anyone (or via simulation / CPI).
+/// Off-chain verifiers can read the same bit directly from the account data.
+#[derive(Accounts)]
+pub struct CheckRevocationStatus<'info> {
+    /// Shard covering `leaf_index`
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+}
+
+/// Returns `true` when the leaf is revoked or burned.
+pub fn handler(ctx: Context<CheckRevocationStatus>, args: CheckRevocationStatusArgs) -> Result<bool> {
+    let list = ctx.accounts.revocation_list.load()?;
+    require!(
+        list.shard_index == RevocationList::shard_for(args.leaf_index),
+        GenuineGradsError::RevocationShardMismatch
+    );
+
+    Ok(list.is_revoked(args.leaf_index))
+}
@@ -0,0 +1,217 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateClaimed;
+use crate::states::{
+    CertificateClaim, CertificateRecord, GlobalConfig, ProgramAllowlist, University,
+    UniversityCollection, UniversityTree, CERTIFICATE_CLAIM_SEED, CERTIFICATE_RECORD_SEED,
+    GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+};
+
+// Bubblegum v2 CPI
+use crate::bubblegum::{LeafProof, TreeCpi};
+use crate::utils::current_merkle_root;
+
+/// The claim keypair's signature over `asset_id || claimant` travels in an Ed25519
+/// program instruction placed right before this one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimCertificateArgs {
+    /// Merkle proof verification (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+
+    /// Optional Bubblegum v2 extras
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCertificate<'info> {
+    /// Graduate's wallet: pays the transaction and receives the leaf
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// University authority (PermanentFreezeDelegate of the collection): required to
+    /// lock the leaf soulbound when the escrow has `lock_on_claim`; pays the CPIs then
+    #[account(mut, address = university.authority @ GenuineGradsError::Unauthorized)]
+    pub university_authority: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    #[account(address = certificate_claim.university @ GenuineGradsError::Unauthorized)]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(mut, address = certificate_claim.merkle_tree @ GenuineGradsError::TreeMismatch)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// Escrow that owns the leaf; closed back to the paying university authority
+    /// PDA = ["certificate_claim", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_CLAIM_SEED, certificate_claim.asset_id.as_ref()],
+        bump = certificate_claim.bump,
+        close = rent_receiver
+    )]
+    pub certificate_claim: Account<'info, CertificateClaim>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_claim.asset_id.as_ref()],
+        bump = certificate_record.bump
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// CHECK: receives the escrow's rent
+    #[account(mut, address = certificate_claim.authority @ GenuineGradsError::Unauthorized)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// CHECK: instructions sysvar, read for the preceding Ed25519 signature check
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimCertificate<'info>>, args: ClaimCertificateArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+
+    // --- Claim checks ---
+    let now = Clock::get()?.unix_timestamp;
+    let claim = &ctx.accounts.certificate_claim;
+    require!(now < claim.expires_at, GenuineGradsError::ClaimExpired);
+    claim.verify_claimant(&ctx.accounts.instructions_sysvar, &ctx.accounts.claimant.key())?;
+    require!(args.nonce == ctx.accounts.certificate_record.nonce, GenuineGradsError::AssetIdMismatch);
+    require!(!ctx.remaining_accounts.is_empty(), GenuineGradsError::MissingMerkleProof);
+    let lock = claim.lock_on_claim;
+    require!(
+        !lock || ctx.accounts.university_authority.is_some(),
+        GenuineGradsError::MissingUniversityAuthority
+    );
+    let payer = match &ctx.accounts.university_authority {
+        Some(authority) => authority.to_account_info(),
+        None => ctx.accounts.claimant.to_account_info(),
+    };
+
+    // --- CPI: TransferV2 signed by the escrow PDA (current leaf owner) ---
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &payer,
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf = LeafProof {
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    let asset_id = claim.asset_id;
+    let escrow_seeds: &[&[u8]] = &[CERTIFICATE_CLAIM_SEED, asset_id.as_ref(), &[claim.bump]];
+    tree.transfer_from_escrow(
+        &ctx.accounts.certificate_claim.to_account_info(),
+        escrow_seeds,
+        &ctx.accounts.claimant.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    // --- CPI: SetNonTransferableV2 on the claimed leaf, signed by the university authority ---
+    // Only the owner/delegate changed, so the same proof path applies against the new root.
+    if lock {
+        let university_tree = &ctx.accounts.university_tree;
+        let claimed = LeafProof {
+            root: current_merkle_root(
+                &ctx.accounts.merkle_tree,
+                university_tree.max_depth,
+                university_tree.max_buffer_size,
+            )?,
+            ..leaf
+        };
+        tree.set_leaf_non_transferable(&ctx.accounts.claimant.to_account_info(), &claimed, ctx.remaining_accounts)?;
+    }
+
+    // --- Record the new owner (escrow is closed by Anchor) ---
+    let rec = &mut ctx.accounts.certificate_record;
+    rec.recipient = ctx.accounts.claimant.key();
+    rec.is_non_transferable = lock;
+
+    emit!(CertificateClaimed {
+        university: rec.university,
+        asset_id,
+        escrow: ctx.accounts.certificate_claim.key(),
+        claimant: rec.recipient,
+        claimed_at: now,
+        non_transferable: lock,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    Cohort, GlobalConfig, University, COHORT_SEED, COHORT_TREE_DEPTH, GLOBAL_CONFIG_SEED,
+    MAX_PROGRAMME_LEN, UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CohortCreated;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCohortArgs {
+    /// Programme name (at most MAX_PROGRAMME_LEN bytes, part of the PDA)
+    pub programme: String,
+    pub year: u16,
+    /// Degree conferral date (unix seconds)
+    pub conferral_date: i64,
+    /// Graduates expected in this cohort
+    pub expected_count: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateCohortArgs)]
+pub struct CreateCohort<'info> {
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["cohort", university, programme, year]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + Cohort::INIT_SPACE,
+        seeds = [COHORT_SEED, university.key().as_ref(), args.programme.as_bytes(), &args.year.to_le_bytes()],
+        bump
+    )]
+    pub cohort: Account<'info, Cohort>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateCohort>, args: CreateCohortArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require!(
+        !args.programme.is_empty() && args.programme.len() <= MAX_PROGRAMME_LEN,
+        GenuineGradsError::InvalidCohort
+    );
+    require!(
+        args.expected_count > 0 && (args.expected_count as u64) <= 1u64 << COHORT_TREE_DEPTH,
+        GenuineGradsError::InvalidCohort
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let cohort = &mut ctx.accounts.cohort;
+    cohort.university = ctx.accounts.university.key();
+    cohort.programme = args.programme;
+    cohort.year = args.year;
+    cohort.conferral_date = args.conferral_date;
+    cohort.expected_count = args.expected_count;
+    cohort.issued_count = 0;
+    cohort.revoked_count = 0;
+    cohort.sealed = false;
+    cohort.sealed_at = 0;
+    cohort.root = [0u8; 32];
+    cohort.frontier = [[0u8; 32]; COHORT_TREE_DEPTH];
+    cohort.created_at = now;
+    cohort.bump = ctx.bumps.cohort;
+
+    emit!(CohortCreated {
+        university: cohort.university,
+        cohort: cohort.key(),
+        programme: cohort.programme.clone(),
+        year: cohort.year,
+        conferral_date: cohort.conferral_date,
+        expected_count: cohort.expected_count,
+        created_at: now,
+    });
+
+    Ok(())
+}
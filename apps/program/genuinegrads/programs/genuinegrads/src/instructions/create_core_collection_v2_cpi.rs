@@ -4,15 +4,15 @@ use anchor_lang::prelude::*;
 use crate::errors::GenuineGradsError;
 use crate::events::CollectionCreatedV2;
 use crate::states::{
-    GlobalConfig, University, UniversityCollection,
-    GLOBAL_CONFIG_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED,
+    GlobalConfig, ProgramAllowlist, University, UniversityCollection,
+    GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, RECOVERY_DELEGATE_SEED, UNIVERSITY_SEED,
+    UNIVERSITY_COLLECTION_SEED,
 };
 
 use mpl_core::{
-    ID as MPL_CORE_ID,
     types::{
-        Plugin, PluginAuthorityPair,
-        BubblegumV2, PermanentFreezeDelegate, PermanentBurnDelegate,
+        Plugin, PluginAuthority, PluginAuthorityPair,
+        BubblegumV2, PermanentFreezeDelegate, PermanentBurnDelegate, PermanentTransferDelegate,
     },
     instructions::CreateCollectionV2CpiBuilder
 };
@@ -40,6 +40,14 @@ pub struct CreateCoreCollectionV2Cpi<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
     /// University must already be registered and ACTIVE.
     /// PDA: ["university", university_authority]
     #[account(
@@ -69,10 +77,13 @@ pub struct CreateCoreCollectionV2Cpi<'info> {
     #[account(mut)]
     pub core_collection: Signer<'info>, 
 
+    /// CHECK: program PDA set as the PermanentTransferDelegate authority; never holds data
+    /// PDA: ["recovery_delegate", core_collection]
+    #[account(seeds = [RECOVERY_DELEGATE_SEED, core_collection.key().as_ref()], bump)]
+    pub recovery_delegate: UncheckedAccount<'info>,
+
     /// The MPL Core program
-    /// We keep it as an UncheckedAccount and enforce the well-known ID below.
-    /// You can also wrap with a typed Program if you prefer.
-    /// CHECK: program ID checked against constant
+    /// CHECK: program ID checked against ProgramAllowlist
     pub mpl_core_program: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
@@ -85,9 +96,13 @@ pub fn handler(
     // Basic checks
     require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
     require!(ctx.accounts.university.is_active, GenuineGradsError::UniversityInactive);
-    require_keys_eq!(ctx.accounts.mpl_core_program.key(), MPL_CORE_ID, GenuineGradsError::InvalidProgramExecutable);
-    require!(args.name.len() > 0 && args.name.len() <= 80, GenuineGradsError::InvalidName);
-    require!(args.uri.len() > 0 && args.uri.len() <= 256, GenuineGradsError::InvalidUri);
+    require_keys_eq!(
+        ctx.accounts.mpl_core_program.key(),
+        ctx.accounts.program_allowlist.programs.mpl_core,
+        GenuineGradsError::InvalidProgramExecutable
+    );
+    require!(!args.name.is_empty() && args.name.len() <= 80, GenuineGradsError::InvalidName);
+    require!(!args.uri.is_empty() && args.uri.len() <= 256, GenuineGradsError::InvalidUri);
     
 
     let ua = &ctx.accounts.university_authority;
@@ -114,6 +129,14 @@ pub fn handler(
             plugin: Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
             authority: None,
         },
+        // Wallet recovery: only our PDA can transfer, and only recover_certificate signs
+        // with it, so the university authority cannot move leaves outside the timelock.
+        PluginAuthorityPair {
+            plugin: Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+            authority: Some(PluginAuthority::Address {
+                address: ctx.accounts.recovery_delegate.key(),
+            }),
+        },
     ];
 
     cpi.payer(payer);
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::states::{ProofRequest, PROOF_REQUEST_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::ProofRequestCreated;
+use crate::groth16::{is_field_element, to_field_element};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateProofRequestArgs {
+    /// Fresh per request, e.g. random bytes (part of the PDA)
+    pub nonce: [u8; 32],
+    /// Require this achievement (sha256(code) mod r, big-endian)
+    pub achievement_hash: Option<[u8; 32]>,
+    /// Unix seconds; must be in the future
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateProofRequestArgs)]
+pub struct CreateProofRequest<'info> {
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    /// PDA = ["proof_request", verifier, nonce]
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + ProofRequest::INIT_SPACE,
+        seeds = [PROOF_REQUEST_SEED, verifier.key().as_ref(), args.nonce.as_ref()],
+        bump
+    )]
+    pub proof_request: Account<'info, ProofRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a request; the student proves against `context` and submits the proof.
+pub fn handler(ctx: Context<CreateProofRequest>, args: CreateProofRequestArgs) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(achievement_hash) = args.achievement_hash {
+        require!(is_field_element(&achievement_hash), GenuineGradsError::InvalidPublicInput);
+    }
+    if let Some(expires_at) = args.expires_at {
+        require!(expires_at > now, GenuineGradsError::InvalidProofRequest);
+    }
+
+    let request_key = ctx.accounts.proof_request.key();
+    let request = &mut ctx.accounts.proof_request;
+    request.verifier = ctx.accounts.verifier.key();
+    request.nonce = args.nonce;
+    request.context = to_field_element(hash(request_key.as_ref()).to_bytes());
+    request.achievement_hash = args.achievement_hash;
+    request.expires_at = args.expires_at;
+    request.verified_count = 0;
+    request.created_at = now;
+    request.bump = ctx.bumps.proof_request;
+
+    emit!(ProofRequestCreated {
+        verifier: request.verifier,
+        proof_request: request_key,
+        context: request.context,
+        achievement_hash: request.achievement_hash,
+        expires_at: request.expires_at,
+        created_at: now,
+    });
+
+    Ok(())
+}
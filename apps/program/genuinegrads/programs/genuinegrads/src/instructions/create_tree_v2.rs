@@ -4,14 +4,12 @@ use anchor_lang::prelude::*;
 use crate::errors::GenuineGradsError;
 use crate::events::TreeCreatedV2;
 use crate::states::{
-    GlobalConfig, University, UniversityTree,
-    GLOBAL_CONFIG_SEED, UNIVERSITY_SEED, UNIVERSITY_TREE_SEED,
+    GlobalConfig, ProgramAllowlist, University, UniversityTree,
+    GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_TREE_SEED,
 };
 
 use mpl_bubblegum::instructions::CreateTreeConfigV2CpiBuilder;
 
-use crate::utils::{Noop, SplAccountCompression, MplBubblegum};
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateTreeV2Args {
     /// Merkle max depth (e.g., 14, 20, 24)
@@ -36,6 +34,14 @@ pub struct CreateTreeV2<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
     /// University must be ACTIVE and tied to the same admin
     /// PDA = ["university", university_authority]
     #[account(
@@ -69,13 +75,17 @@ pub struct CreateTreeV2<'info> {
     #[account(mut)]
     pub tree_config: UncheckedAccount<'info>,
 
-    pub bubblegum_program: Program<'info, MplBubblegum>,
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
 
-    /// SPL Account Compression program
-    pub compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK: SPL Account Compression program, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
 
-    /// SPL Noop program (log wrapper)
-    pub log_wrapper: Program<'info, Noop>,
+    /// CHECK: SPL Noop program (log wrapper), ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -84,14 +94,17 @@ pub fn handler(ctx: Context<CreateTreeV2>, args: CreateTreeV2Args) -> Result<()>
     // Guard rails
     require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
     require!(ctx.accounts.university.is_active, GenuineGradsError::UniversityInactive);
-    require_keys_eq!(ctx.accounts.bubblegum_program.key(), MplBubblegum::id(), GenuineGradsError::InvalidProgramExecutable);
-    require_keys_eq!(ctx.accounts.compression_program.key(), SplAccountCompression::id(), GenuineGradsError::InvalidProgramExecutable);
-    require_keys_eq!(ctx.accounts.log_wrapper.key(), Noop::id(), GenuineGradsError::InvalidProgramExecutable);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = &ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
     
     // Validate Bubblegum's tree_config PDA matches expected derivation
     let (expected_tree_config, _bump) = Pubkey::find_program_address(
         &[ctx.accounts.merkle_tree.key.as_ref()],
-        &MplBubblegum::id()
+        &programs.bubblegum
     );
     require_keys_eq!(
         ctx.accounts.tree_config.key(),
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    GlobalConfig, VerifyingKey, GLOBAL_CONFIG_SEED, MAX_CIRCUIT_ID_LEN, MAX_PUBLIC_INPUTS,
+    VERIFYING_KEY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::VerifyingKeyCreated;
+use crate::instructions::verify_achievement_proof::{ACH_MEMBER_CIRCUIT_ID, ACH_MEMBER_PUBLIC_INPUTS};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateVerifyingKeyArgs {
+    /// Circuit family (at most MAX_CIRCUIT_ID_LEN bytes, part of the PDA)
+    pub circuit_id: String,
+    pub version: u16,
+    /// Number of public inputs; fixes the key data length
+    pub public_inputs: u8,
+    /// sha256 of the complete key data, checked on finalize
+    pub key_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateVerifyingKeyArgs)]
+pub struct CreateVerifyingKey<'info> {
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump,
+        constraint = global_config.owner == super_admin.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["verifying_key", super_admin, circuit_id, version]
+    #[account(
+        init,
+        payer = super_admin,
+        space = 8 + VerifyingKey::INIT_SPACE,
+        seeds = [VERIFYING_KEY_SEED, super_admin.key().as_ref(), args.circuit_id.as_bytes(), &args.version.to_le_bytes()],
+        bump
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateVerifyingKey>, args: CreateVerifyingKeyArgs) -> Result<()> {
+    require!(
+        !args.circuit_id.is_empty() && args.circuit_id.len() <= MAX_CIRCUIT_ID_LEN,
+        GenuineGradsError::InvalidVerifyingKey
+    );
+    require!(
+        args.public_inputs > 0 && args.public_inputs <= MAX_PUBLIC_INPUTS,
+        GenuineGradsError::InvalidVerifyingKey
+    );
+    // verify_achievement_proof only takes the proof request layout (v1 keys are refused)
+    if args.circuit_id == ACH_MEMBER_CIRCUIT_ID {
+        require!(args.public_inputs == ACH_MEMBER_PUBLIC_INPUTS, GenuineGradsError::InvalidVerifyingKey);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let vk = &mut ctx.accounts.verifying_key;
+    vk.admin = ctx.accounts.super_admin.key();
+    vk.circuit_id = args.circuit_id;
+    vk.version = args.version;
+    vk.public_inputs = args.public_inputs;
+    vk.key_hash = args.key_hash;
+    vk.data = Vec::new();
+    vk.finalized = false;
+    vk.deprecated = false;
+    vk.created_at = now;
+    vk.finalized_at = 0;
+    vk.deprecated_at = 0;
+    vk.bump = ctx.bumps.verifying_key;
+
+    emit!(VerifyingKeyCreated {
+        admin: vk.admin,
+        verifying_key: vk.key(),
+        circuit_id: vk.circuit_id.clone(),
+        version: vk.version,
+        public_inputs: vk.public_inputs,
+        key_hash: vk.key_hash,
+        created_at: now,
+    });
+
+    Ok(())
+}
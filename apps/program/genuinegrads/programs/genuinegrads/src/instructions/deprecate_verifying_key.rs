@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::states::{VerifyingKey, VERIFYING_KEY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::VerifyingKeyDeprecated;
+
+#[derive(Accounts)]
+pub struct DeprecateVerifyingKey<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["verifying_key", super_admin, circuit_id, version]
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, super_admin.key().as_ref(), verifying_key.circuit_id.as_bytes(), &verifying_key.version.to_le_bytes()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.admin == super_admin.key() @ GenuineGradsError::Unauthorized,
+        constraint = verifying_key.finalized @ GenuineGradsError::VerifyingKeyNotFinalized,
+        constraint = !verifying_key.deprecated @ GenuineGradsError::VerifyingKeyDeprecated
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+}
+
+/// Marks a superseded circuit version. Proofs still verify against it; the
+/// `key_deprecated` flag on ZkProofVerified lets verifiers decide what that's worth.
+pub fn handler(ctx: Context<DeprecateVerifyingKey>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vk = &mut ctx.accounts.verifying_key;
+    vk.deprecated = true;
+    vk.deprecated_at = now;
+
+    emit!(VerifyingKeyDeprecated {
+        admin: vk.admin,
+        verifying_key: vk.key(),
+        circuit_id: vk.circuit_id.clone(),
+        version: vk.version,
+        deprecated_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::states::{VerifyingKey, VERIFYING_KEY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::VerifyingKeyFinalized;
+
+#[derive(Accounts)]
+pub struct FinalizeVerifyingKey<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["verifying_key", super_admin, circuit_id, version]
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, super_admin.key().as_ref(), verifying_key.circuit_id.as_bytes(), &verifying_key.version.to_le_bytes()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.admin == super_admin.key() @ GenuineGradsError::Unauthorized,
+        constraint = !verifying_key.finalized @ GenuineGradsError::VerifyingKeyFinalized
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+}
+
+/// Locks the key once every byte is uploaded and matches `key_hash`. There is no way back.
+pub fn handler(ctx: Context<FinalizeVerifyingKey>) -> Result<()> {
+    let vk = &mut ctx.accounts.verifying_key;
+    require!(
+        vk.groth16().is_some() && hash(&vk.data).to_bytes() == vk.key_hash,
+        GenuineGradsError::InvalidVerifyingKey
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    vk.finalized = true;
+    vk.finalized_at = now;
+
+    emit!(VerifyingKeyFinalized {
+        admin: vk.admin,
+        verifying_key: vk.key(),
+        circuit_id: vk.circuit_id.clone(),
+        version: vk.version,
+        key_hash: vk.key_hash,
+        finalized_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::states::{GlobalConfig, MintConsent, University, GLOBAL_CONFIG_SEED, MINT_CONSENT_SEED, UNIVERSITY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::MintConsentGiven;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GiveMintConsentArgs {
+    /// name_uri_hash(name, uri) of the certificate the student accepts
+    pub credential_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: GiveMintConsentArgs)]
+pub struct GiveMintConsent<'info> {
+    /// Student wallet (future leaf owner) is also the payer
+    #[account(mut)]
+    pub student: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// University that will issue the certificate; must be ACTIVE under this admin
+    /// PDA = ["university", university.authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university.authority.as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["mint_consent", university, student, credential_hash]
+    #[account(
+        init,
+        payer = student,
+        space = 8 + MintConsent::INIT_SPACE,
+        seeds = [MINT_CONSENT_SEED, university.key().as_ref(), student.key().as_ref(), args.credential_hash.as_ref()],
+        bump
+    )]
+    pub mint_consent: Account<'info, MintConsent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<GiveMintConsent>, args: GiveMintConsentArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let consent = &mut ctx.accounts.mint_consent;
+    consent.student = ctx.accounts.student.key();
+    consent.university = ctx.accounts.university.key();
+    consent.credential_hash = args.credential_hash;
+    consent.asset_id = None;
+    consent.created_at = now;
+    consent.bump = ctx.bumps.mint_consent;
+
+    emit!(MintConsentGiven {
+        university: consent.university,
+        student: consent.student,
+        credential_hash: consent.credential_hash,
+        given_at: now,
+    });
+
+    Ok(())
+}
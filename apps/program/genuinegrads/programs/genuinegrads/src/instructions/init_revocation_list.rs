@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    GlobalConfig, RevocationList, University, UniversityTree,
+    GLOBAL_CONFIG_SEED, REVOCATION_LIST_SEED, UNIVERSITY_SEED, UNIVERSITY_TREE_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::RevocationListCreated;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitRevocationListArgs {
+    /// Shard to create (covers leaves [shard_index * 16384, (shard_index + 1) * 16384))
+    pub shard_index: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitRevocationListArgs)]
+pub struct InitRevocationList<'info> {
+    /// University authority is also the payer
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, university_tree.merkle_tree.as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// PDA = ["revocation_list", university_tree, shard_index]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RevocationList::LEN,
+        seeds = [REVOCATION_LIST_SEED, university_tree.key().as_ref(), &args.shard_index.to_le_bytes()],
+        bump
+    )]
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitRevocationList>, args: InitRevocationListArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let tree = &ctx.accounts.university_tree;
+    require!(
+        args.shard_index < RevocationList::shard_count(tree.max_depth),
+        GenuineGradsError::RevocationShardMismatch
+    );
+
+    let mut list = ctx.accounts.revocation_list.load_init()?;
+    list.university_tree = tree.key();
+    list.merkle_tree = tree.merkle_tree;
+    list.shard_index = args.shard_index;
+    list.revoked_count = 0;
+    list.updated_at = Clock::get()?.unix_timestamp;
+    list.bump = ctx.bumps.revocation_list;
+
+    emit!(RevocationListCreated {
+        university: ctx.accounts.university.key(),
+        university_tree: tree.key(),
+        merkle_tree: tree.merkle_tree,
+        revocation_list: ctx.accounts.revocation_list.key(),
+        shard_index: args.shard_index,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 // programs/genuinegrads/src/instructions/initialize_config.rs
 use anchor_lang::prelude::*;
-use crate::states::{GlobalConfig, GLOBAL_CONFIG_SEED};
+use crate::states::{GlobalConfig, GLOBAL_CONFIG_SEED, URI_SCHEME_ALL};
 use crate::events::ConfigInitialized;
 
 #[derive(Accounts)]
@@ -29,6 +29,7 @@ pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
 
     gc.owner = ctx.accounts.super_admin.key();
     gc.frozen = false;
+    gc.allowed_uri_schemes = URI_SCHEME_ALL;
     gc.bump = bump;
 
     emit!(ConfigInitialized { 
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    ExternalProgramIds, GlobalConfig, ProgramAllowlist, GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::ProgramAllowlistInitialized;
+use crate::utils::{MPL_BUBBLEGUM_ID, MPL_CORE_ID, NOOP_ID, SPL_ACCOUNT_COMPRESSION_ID};
+
+#[derive(Accounts)]
+pub struct InitializeProgramAllowlist<'info> {
+    /// Super admin is also the payer
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// One-time allowlist for this config
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        init,
+        payer = super_admin,
+        space = 8 + ProgramAllowlist::INIT_SPACE,
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeProgramAllowlist>) -> Result<()> {
+    let gc = &ctx.accounts.global_config;
+    require_keys_eq!(ctx.accounts.super_admin.key(), gc.owner, GenuineGradsError::Unauthorized);
+
+    let now = Clock::get()?.unix_timestamp;
+    let list = &mut ctx.accounts.program_allowlist;
+
+    // Seed with the IDs of the cluster feature this binary was built with (utils::cluster)
+    list.admin = gc.owner;
+    list.version = 1;
+    list.programs = ExternalProgramIds {
+        bubblegum: MPL_BUBBLEGUM_ID,
+        mpl_core: MPL_CORE_ID,
+        account_compression: SPL_ACCOUNT_COMPRESSION_ID,
+        noop: NOOP_ID,
+    };
+    list.pending = None;
+    list.pending_eta = 0;
+    list.updated_at = now;
+    list.bump = ctx.bumps.program_allowlist;
+
+    emit!(ProgramAllowlistInitialized {
+        admin: list.admin,
+        program_allowlist: list.key(),
+        version: list.version,
+        programs: list.programs,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::error::ErrorCode;
+use crate::states::{GlobalConfig, GLOBAL_CONFIG_SEED, URI_SCHEME_ALL};
+use crate::errors::GenuineGradsError;
+use crate::utils::grow_account;
+
+/// Grows a GlobalConfig written before `allowed_uri_schemes` was appended. Grown configs
+/// accept every scheme, matching `initialize_config`.
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    /// Super admin pays for the extra space
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    /// CHECK: old layout cannot be deserialized as `GlobalConfig` until grown; owner and
+    /// discriminator are checked in the handler
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+    // Not gated on `frozen`: migrations are maintenance work
+    let info = ctx.accounts.global_config.to_account_info();
+    require!(
+        info.try_borrow_data()?.starts_with(GlobalConfig::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let new_len = 8 + GlobalConfig::INIT_SPACE;
+    let grown = info.data_len() < new_len;
+    if grown {
+        grow_account(
+            &info,
+            &ctx.accounts.super_admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_len,
+        )?;
+    }
+
+    let mut gc = GlobalConfig::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require_keys_eq!(gc.owner, ctx.accounts.super_admin.key(), GenuineGradsError::Unauthorized);
+    if grown {
+        gc.allowed_uri_schemes = URI_SCHEME_ALL;
+        gc.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+
+use crate::errors::GenuineGradsError;
+use crate::events::RecoveryDelegateMigrated;
+use crate::states::{
+    GlobalConfig, ProgramAllowlist, University, UniversityCollection, GLOBAL_CONFIG_SEED,
+    PROGRAM_ALLOWLIST_SEED, RECOVERY_DELEGATE_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED,
+};
+
+use mpl_core::{
+    fetch_collection_plugin,
+    instructions::ApproveCollectionPluginAuthorityV1CpiBuilder,
+    types::{PermanentTransferDelegate, PluginAuthority, PluginType},
+};
+
+/// Moves an existing collection's PermanentTransferDelegate to the recovery delegate PDA.
+/// Collections whose plugin defaulted to the update authority have its authority re-approved
+/// to the PDA. Collections created without the plugin are refused: MPL Core only accepts
+/// permanent plugins at collection creation.
+#[derive(Accounts)]
+pub struct MigrateRecoveryDelegate<'info> {
+    /// University authority is payer & update authority of the collection
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// CHECK: program PDA that becomes the PermanentTransferDelegate authority
+    /// PDA = ["recovery_delegate", core_collection]
+    #[account(seeds = [RECOVERY_DELEGATE_SEED, core_collection.key().as_ref()], bump)]
+    pub recovery_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program, ID checked against the allowlist
+    #[account(executable)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateRecoveryDelegate>) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require_keys_eq!(
+        ctx.accounts.mpl_core_program.key(),
+        ctx.accounts.program_allowlist.programs.mpl_core,
+        GenuineGradsError::InvalidProgramExecutable
+    );
+
+    let delegate = ctx.accounts.recovery_delegate.key();
+    let new_authority = PluginAuthority::Address { address: delegate };
+
+    let authority = &ctx.accounts.university_authority.to_account_info();
+    let collection = &ctx.accounts.core_collection.to_account_info();
+    let system_program = &ctx.accounts.system_program.to_account_info();
+
+    let current = fetch_collection_plugin::<PermanentTransferDelegate>(
+        collection,
+        PluginType::PermanentTransferDelegate,
+    )
+    .ok()
+    .map(|(plugin_authority, _, _)| plugin_authority);
+
+    match migration_step(current.as_ref(), &delegate)? {
+        MigrationStep::AlreadyMigrated => {}
+        MigrationStep::ApproveToDelegate => {
+            ApproveCollectionPluginAuthorityV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
+                .collection(collection)
+                .payer(authority)
+                .authority(Some(authority))
+                .system_program(system_program)
+                .plugin_type(PluginType::PermanentTransferDelegate)
+                .new_authority(new_authority)
+                .invoke()?;
+        }
+    }
+
+    emit!(RecoveryDelegateMigrated {
+        university: ctx.accounts.university.key(),
+        collection: collection.key(),
+        recovery_delegate: delegate,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MigrationStep {
+    /// The PDA already holds the plugin: nothing to do
+    AlreadyMigrated,
+    /// The plugin exists under another authority (the update authority): hand it to the PDA
+    ApproveToDelegate,
+}
+
+/// Picks the migration for a collection from its current PermanentTransferDelegate
+/// authority (`None` when the collection has no such plugin).
+fn migration_step(current: Option<&PluginAuthority>, delegate: &Pubkey) -> Result<MigrationStep> {
+    match current {
+        Some(PluginAuthority::Address { address }) if address == delegate => Ok(MigrationStep::AlreadyMigrated),
+        Some(_) => Ok(MigrationStep::ApproveToDelegate),
+        // Permanent plugins cannot be added after creation
+        None => err!(GenuineGradsError::RecoveryDelegateUnsupported),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Collections that predate the plugin cannot be set up on localnet, so the
+    // branches are covered here; the end-to-end no-op runs in the TS suite.
+    #[test]
+    fn approves_plugin_held_by_update_authority() {
+        let delegate = Pubkey::new_unique();
+        assert_eq!(
+            migration_step(Some(&PluginAuthority::UpdateAuthority), &delegate).unwrap(),
+            MigrationStep::ApproveToDelegate
+        );
+        let other = PluginAuthority::Address { address: Pubkey::new_unique() };
+        assert_eq!(migration_step(Some(&other), &delegate).unwrap(), MigrationStep::ApproveToDelegate);
+    }
+
+    #[test]
+    fn skips_collection_already_migrated() {
+        let delegate = Pubkey::new_unique();
+        let current = PluginAuthority::Address { address: delegate };
+        assert_eq!(migration_step(Some(&current), &delegate).unwrap(), MigrationStep::AlreadyMigrated);
+    }
+
+    #[test]
+    fn refuses_collection_without_plugin() {
+        let err = migration_step(None, &Pubkey::new_unique()).unwrap_err();
+        assert_eq!(err, GenuineGradsError::RecoveryDelegateUnsupported.into());
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::error::ErrorCode;
+use crate::states::{GlobalConfig, University, GLOBAL_CONFIG_SEED, UNIVERSITY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::utils::grow_account;
+
+/// Grows a University account written before `require_student_consent` was appended.
+/// The new byte is zeroed, so migrated universities keep the policy off.
+#[derive(Accounts)]
+pub struct MigrateUniversity<'info> {
+    /// Super admin pays for the extra space
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    /// GlobalConfig bound to this super admin
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: only used to derive the university PDA
+    pub university_authority: UncheckedAccount<'info>,
+
+    /// CHECK: old layout cannot be deserialized as `University` until grown; owner and
+    /// discriminator are checked in the handler
+    /// PDA = ["university", university_authority]
+    #[account(
+        mut,
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub university: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateUniversity>) -> Result<()> {
+    // Not gated on `frozen`: migrations are maintenance work
+    let info = ctx.accounts.university.to_account_info();
+    require!(
+        info.try_borrow_data()?.starts_with(University::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let new_len = 8 + University::INIT_SPACE;
+    if info.data_len() < new_len {
+        grow_account(
+            &info,
+            &ctx.accounts.super_admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_len,
+        )?;
+    }
+
+    // Grown account must now read as a University of this admin
+    let uni = University::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require_keys_eq!(uni.admin, ctx.accounts.global_config.owner, GenuineGradsError::Unauthorized);
+    require_keys_eq!(uni.authority, ctx.accounts.university_authority.key(), GenuineGradsError::Unauthorized);
+
+    Ok(())
+}
@@ -4,34 +4,73 @@ use anchor_lang::prelude::*;
 use crate::errors::GenuineGradsError;
 use crate::events::CertificateMintedV2;
 use crate::states::{
-    GlobalConfig, University, UniversityCollection, UniversityTree,
-    GLOBAL_CONFIG_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+    CertificateClaim, CertificateRecord, CertificateStatus, Cohort, CollectionPolicy, CredentialId,
+    DocumentDigest, GlobalConfig, MintConsent, ProgramAllowlist, University, UniversityCollection, UniversityTree, leaf_metadata_settings, validate_documents,
+    load_co_issuers, validate_validity_window,
+    CERTIFICATE_CLAIM_SEED, CERTIFICATE_RECORD_SEED, COHORT_SEED, COLLECTION_POLICY_SEED,
+    CREDENTIAL_ID_SEED,
+    GLOBAL_CONFIG_SEED,
+    MAX_CO_ISSUERS, MAX_NAME_LEN, MAX_URI_LEN, MINT_CONSENT_SEED, PROGRAM_ALLOWLIST_SEED,
+    UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
 };
 
 // Bubblegum + deps
-use mpl_bubblegum::ID as BUBBLEGUM_ID;
-use mpl_bubblegum::instructions::MintV2CpiBuilder;
-use mpl_bubblegum::types::{Creator, TokenStandard};
+use mpl_bubblegum::types::{MetadataArgsV2, TokenStandard};
+use crate::bubblegum::TreeCpi;
 
-// SPL-Compression + Noop
-use crate::utils::{Noop, SplAccountCompression};
+// Bubblegum's MPL Core CPI signer + leaf helpers
+use crate::utils::{
+    asset_id, certificate_creators, mpl_core_cpi_signer, name_uri_hash, tree_num_minted,
+};
 
-// MPL Core (for collection + CPI signer)
-use mpl_core::ID as MPL_CORE_ID;
+/// Escrow parameters for graduates without a wallet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimArgs {
+    /// Public key of the one-time claim keypair handed to the graduate
+    pub claim_pubkey: Pubkey,
+    /// Deadline for claim_certificate; afterwards reclaim_certificate applies
+    pub expires_at: i64,
+    /// Keep the leaf transferable once claimed. Otherwise claim_certificate makes it
+    /// soulbound and needs the university authority's signature.
+    pub transferable: bool,
+}
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MintCertificateArgs {
     /// Display name for the certificate (e.g., "BSc Computer Science — 2025")
     pub name: String,
-    /// Off-chain JSON metadata for the certificate
+    /// Off-chain JSON metadata for the certificate (scheme allowlisted in GlobalConfig)
     pub uri: String,
+    /// SHA-256 of the metadata JSON served at `uri`
+    pub metadata_hash: [u8; 32],
+    /// Optional digests of the PDF diploma, transcript, ... (at most MAX_DOCUMENTS)
+    pub documents: Vec<DocumentDigest>,
     /// Recipient (student) who will own the compressed NFT leaf
     pub recipient: Pubkey,
     /// If true, attempt collection-verified mint under the university's Core collection
     pub attach_collection: bool,
+    /// Asset id of the leaf about to be minted (derived from tree_config.num_minted)
+    pub asset_id: Pubkey,
+    /// Certificates are locked soulbound (SetNonTransferableV2) right after minting;
+    /// the new leaf's proof follows the co-issuer accounts. `true` skips the lock.
+    pub transferable: bool,
+    /// Optional validity window (unix seconds); `None` means unbounded
+    pub valid_from: Option<i64>,
+    pub valid_until: Option<i64>,
+    /// Mint into a claim escrow instead of a known wallet.
+    /// `recipient` must then be the `certificate_claim` PDA and `transferable` set.
+    pub claim: Option<ClaimArgs>,
+    /// Partner University accounts of a joint degree (at most MAX_CO_ISSUERS).
+    /// Remaining accounts start with one (University, authority signer or
+    /// CoIssuanceApproval) pair per co-issuer, followed by the soulbound proof.
+    pub co_issuers: Vec<Pubkey>,
+    /// sha256 of the backend credential id; requires `credential_id_record`.
+    /// Makes the mint idempotent: a second mint of the same id fails.
+    pub credential_id: Option<[u8; 32]>,
 }
 
 #[derive(Accounts)]
+#[instruction(args: MintCertificateArgs)]
 pub struct MintCertificateV2<'info> {
     /// University authority is payer & operational authority
     #[account(mut)]
@@ -43,6 +82,14 @@ pub struct MintCertificateV2<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
     /// University must be ACTIVE, tied to this authority & admin
     /// PDA = ["university", university_authority]
     #[account(
@@ -84,49 +131,174 @@ pub struct MintCertificateV2<'info> {
     #[account(mut)]
     pub core_collection: UncheckedAccount<'info>,
 
-    /// CHECK: This account is used for its pubkey only
-    #[account(address = MPL_CORE_ID)]
+    /// CHECK: Per-collection policy (symbol, naming/URI rules, mutability), enforced once it exists.
+    /// PDA = ["collection_policy", core_collection]
+    #[account(seeds = [COLLECTION_POLICY_SEED, core_collection.key().as_ref()], bump)]
+    pub collection_policy: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program, ID checked against the allowlist
+    #[account(executable)]
     pub mpl_core_program: UncheckedAccount<'info>,
 
-    /// CHECK: MPL Core CPI signer PDA for Bubblegum:
-    /// seeds = ["cpi_signer", core_collection, BUBBLEGUM_ID] under program MPL_CORE_ID
+    /// CHECK: Bubblegum's MPL Core CPI signer PDA:
+    /// seeds = ["mpl_core_cpi_signer"] under the allowlisted Bubblegum program
     /// We verify its derivation below. This must be passed when attach_collection = true.
     /// In practice, Bubblegum uses this PDA as the collection authority via CPI.
     pub mpl_core_cpi_signer: UncheckedAccount<'info>,
 
-    /// CHECK: only used as a pubkey
+    /// CHECK: only used as a pubkey; may co-sign to give consent
     pub recipient: UncheckedAccount<'info>,
 
-    /// Bubblegum program
-    /// CHECK: ID verified
+    /// Our program's record for this certificate
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CertificateRecord::INIT_SPACE,
+        seeds = [CERTIFICATE_RECORD_SEED, args.asset_id.as_ref()],
+        bump
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// Claim escrow (only for `args.claim`); it owns the leaf until claimed
+    /// PDA = ["certificate_claim", asset_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CertificateClaim::INIT_SPACE,
+        seeds = [CERTIFICATE_CLAIM_SEED, args.asset_id.as_ref()],
+        bump
+    )]
+    pub certificate_claim: Option<Account<'info, CertificateClaim>>,
+
+    /// Student's advance consent (when the university requires consent and the
+    /// recipient does not co-sign); marked used by this mint
+    /// PDA = ["mint_consent", university, recipient, credential_hash]
+    #[account(
+        mut,
+        seeds = [
+            MINT_CONSENT_SEED,
+            university.key().as_ref(),
+            recipient.key().as_ref(),
+            mint_consent.credential_hash.as_ref()
+        ],
+        bump = mint_consent.bump
+    )]
+    pub mint_consent: Option<Account<'info, MintConsent>>,
+
+    /// Credential id → asset id mapping (only for `args.credential_id`);
+    /// `init` fails if the id was already minted
+    /// PDA = ["credential_id", university, credential_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CredentialId::INIT_SPACE,
+        seeds = [
+            CREDENTIAL_ID_SEED,
+            university.key().as_ref(),
+            args.credential_id.unwrap_or_default().as_ref()
+        ],
+        bump
+    )]
+    pub credential_id_record: Option<Account<'info, CredentialId>>,
+
+    /// Graduating cohort the certificate joins (optional; must not be sealed)
+    /// PDA = ["cohort", university, programme, year]
+    #[account(
+        mut,
+        seeds = [COHORT_SEED, university.key().as_ref(), cohort.programme.as_bytes(), &cohort.year.to_le_bytes()],
+        bump = cohort.bump
+    )]
+    pub cohort: Option<Account<'info, Cohort>>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
     pub bubblegum_program: UncheckedAccount<'info>,
 
-    /// SPL Account Compression
-    pub compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
 
-    /// SPL Noop log wrapper
-    pub log_wrapper: Program<'info, Noop>,
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<MintCertificateV2>, args: MintCertificateArgs) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MintCertificateV2<'info>>, args: MintCertificateArgs) -> Result<()> {
     // --- Governance guards ---
     require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
     require!(ctx.accounts.university.is_active, GenuineGradsError::UniversityInactive);
     // Metaplex Bubblegum enforces max 32 chars for name
-    require!(args.name.len() > 0 && args.name.len() <= 32, GenuineGradsError::InvalidName);
-    require!(args.uri.len() > 0 && args.uri.len() <= 200, GenuineGradsError::InvalidUri);
+    require!(!args.name.is_empty() && args.name.len() <= MAX_NAME_LEN, GenuineGradsError::InvalidName);
+    require!(!args.uri.is_empty() && args.uri.len() <= MAX_URI_LEN, GenuineGradsError::InvalidUri);
+    ctx.accounts.global_config.validate_uri(&args.uri)?;
+    require!(args.metadata_hash != [0u8; 32], GenuineGradsError::MissingMetadataHash);
+    validate_documents(&args.documents)?;
+    validate_validity_window(args.valid_from, args.valid_until)?;
+
+    // Claimable certificates: escrow PDA is the recipient; the leaf must stay transferable
+    let now = Clock::get()?.unix_timestamp;
+    match (&args.claim, &ctx.accounts.certificate_claim) {
+        (Some(claim), Some(escrow)) => {
+            require!(claim.expires_at > now, GenuineGradsError::InvalidClaimSetup);
+            require!(claim.claim_pubkey != Pubkey::default(), GenuineGradsError::InvalidClaimSetup);
+            require!(args.transferable, GenuineGradsError::InvalidClaimSetup);
+            require_keys_eq!(ctx.accounts.recipient.key(), escrow.key(), GenuineGradsError::InvalidClaimSetup);
+        }
+        (None, None) => {}
+        _ => return err!(GenuineGradsError::InvalidClaimSetup),
+    }
+
+    // Idempotency key: the id and its mapping account come together
+    require!(
+        args.credential_id.is_some() == ctx.accounts.credential_id_record.is_some(),
+        GenuineGradsError::InvalidCredentialId
+    );
+    require!(args.credential_id != Some([0u8; 32]), GenuineGradsError::InvalidCredentialId);
 
-    // Program IDs
-    require_keys_eq!(ctx.accounts.bubblegum_program.key(), BUBBLEGUM_ID, GenuineGradsError::InvalidProgramExecutable);
-    require_keys_eq!(ctx.accounts.compression_program.key(), SplAccountCompression::id(), GenuineGradsError::InvalidProgramExecutable);
-    require_keys_eq!(ctx.accounts.log_wrapper.key(), Noop::id(), GenuineGradsError::InvalidProgramExecutable);
+    // Student consent: recipient co-signs, or an unused MintConsent names this credential
+    let credential_hash = name_uri_hash(&args.name, &args.uri);
+    if let Some(consent) = &ctx.accounts.mint_consent {
+        require!(consent.asset_id.is_none(), GenuineGradsError::ConsentAlreadyUsed);
+        require!(consent.credential_hash == credential_hash, GenuineGradsError::StudentConsentRequired);
+    }
+    if ctx.accounts.university.require_student_consent {
+        require!(
+            ctx.accounts.recipient.is_signer || ctx.accounts.mint_consent.is_some(),
+            GenuineGradsError::StudentConsentRequired
+        );
+    }
+
+    // Joint degree: co-issuer accounts come first in remaining accounts, the proof after
+    require!(args.co_issuers.len() <= MAX_CO_ISSUERS, GenuineGradsError::TooManyCoIssuers);
+    require!(
+        ctx.remaining_accounts.len() >= 2 * args.co_issuers.len(),
+        GenuineGradsError::MissingRemainingAccounts
+    );
+    let (co_issuer_accounts, proof) = ctx.remaining_accounts.split_at(2 * args.co_issuers.len());
+    let co_issuers = load_co_issuers(
+        ctx.program_id,
+        &ctx.accounts.global_config.owner,
+        &ctx.accounts.university.key(),
+        &credential_hash,
+        &args.asset_id,
+        &args.co_issuers,
+        co_issuer_accounts,
+    )?;
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.mpl_core_program.key(), programs.mpl_core, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
 
     // Verify tree_config PDA belongs to this merkle_tree (Bubblegum convention: ["tree", merkle_tree])
     let (expected_tree_config, _) = Pubkey::find_program_address(
         &[ctx.accounts.merkle_tree.key.as_ref()],
-        &BUBBLEGUM_ID
+        &programs.bubblegum
     );
     require_keys_eq!(
         ctx.accounts.tree_config.key(),
@@ -143,56 +315,135 @@ pub fn handler(ctx: Context<MintCertificateV2>, args: MintCertificateArgs) -> Re
 
     // If attaching collection, verify the MPL Core CPI signer PDA derivation
     if args.attach_collection {
-        require_keys_eq!(ctx.accounts.mpl_core_cpi_signer.key(), pubkey!("CbNY3JiXdXNE9tPNEk1aRZVEkWdj2v7kfJLNQwZZgpXk"), GenuineGradsError::InvalidCoreCpiSigner);
+        require_keys_eq!(ctx.accounts.mpl_core_cpi_signer.key(), mpl_core_cpi_signer(&programs.bubblegum), GenuineGradsError::InvalidCoreCpiSigner);
     }
 
-    let mut cpi = MintV2CpiBuilder::new(&ctx.accounts.bubblegum_program);
-
-    let tree_config = &ctx.accounts.tree_config.to_account_info();
-    let leaf_owner = &&ctx.accounts.recipient.to_account_info();
-    let merkle_tree = &ctx.accounts.merkle_tree.to_account_info();
-    let payer = &ctx.accounts.university_authority.to_account_info();
-    let tree_creator_or_delegate = &ctx.accounts.university_authority.to_account_info();
-    let compression_program = &ctx.accounts.compression_program.to_account_info();
-    let log_wrapper = &ctx.accounts.log_wrapper.to_account_info();
-    let system_program = &ctx.accounts.system_program.to_account_info();
-    let core_collection = &ctx.accounts.core_collection.to_account_info();
+    // The new leaf's nonce is the tree's current mint count; the record is keyed by its asset id
+    let nonce = tree_num_minted(&ctx.accounts.tree_config)?;
+    require_keys_eq!(
+        args.asset_id,
+        asset_id(&programs.bubblegum, ctx.accounts.merkle_tree.key, nonce),
+        GenuineGradsError::AssetIdMismatch
+    );
+
+    // Collection issuance policy: symbol, royalty, naming/URI rules and mutability
+    let (symbol, seller_fee_basis_points, is_mutable) = leaf_metadata_settings(
+        CollectionPolicy::load(&ctx.accounts.collection_policy)?.as_ref(),
+        &args.name,
+        &args.uri,
+    )?;
+
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf_owner = &ctx.accounts.recipient.to_account_info();
     let mpl_core_program = &ctx.accounts.mpl_core_program.to_account_info();
-    let collection_authority = &ctx.accounts.university_authority.to_account_info();
     let mpl_core_cpi_signer = &ctx.accounts.mpl_core_cpi_signer.to_account_info();
 
-    // Required base accounts
-    cpi.tree_config(tree_config);
-    cpi.leaf_owner(leaf_owner);
-    cpi.leaf_delegate(None);
-    cpi.merkle_tree(merkle_tree);
-    cpi.payer(payer);
-    cpi.tree_creator_or_delegate(Some(tree_creator_or_delegate));
-    cpi.compression_program(compression_program);
-    cpi.log_wrapper(log_wrapper);
-    cpi.system_program(system_program);
-    cpi.mpl_core_program(mpl_core_program);
-    cpi.core_collection(Some(core_collection));
-    cpi.mpl_core_cpi_signer(Some(mpl_core_cpi_signer));
-    cpi.collection_authority(Some(collection_authority));
-    cpi.metadata(mpl_bubblegum::types::MetadataArgsV2 {
+    // Co-issuers who signed are verified creators; Bubblegum checks their signatures
+    let co_signers: Vec<_> = co_issuer_accounts
+        .chunks(2)
+        .zip(&co_issuers)
+        .filter(|(_, co)| co.verified)
+        .map(|(pair, _)| (&pair[1], false, true))
+        .collect();
+    let metadata = MetadataArgsV2 {
         name: args.name.clone(),
-        symbol: "GG-CERT".to_string(),
+        symbol: symbol.clone(),
         uri: args.uri.clone(),
-        seller_fee_basis_points: 0,
+        seller_fee_basis_points,
         primary_sale_happened: false,
-        is_mutable: false,
+        is_mutable,
         token_standard: Some(TokenStandard::NonFungible),
         collection: Some(ctx.accounts.core_collection.key()),
-        creators: vec![Creator {
-            address: ctx.accounts.university_authority.key(),
-            verified: true,
-            share: 100,
-        }],
-    });
+        creators: certificate_creators(ctx.accounts.university_authority.key(), &co_issuers),
+    };
+    tree.mint(mpl_core_program, mpl_core_cpi_signer, leaf_owner, metadata.clone(), &co_signers)?;
+
+    // --- CPI: SetNonTransferableV2 (soulbound) ---
+    // The collection's PermanentFreezeDelegate defaults to its update authority,
+    // i.e. the university authority, which signs here.
+    let is_non_transferable = !args.transferable;
+    if is_non_transferable {
+        require!(!proof.is_empty(), GenuineGradsError::MissingMerkleProof);
+        tree.set_non_transferable(&ctx.accounts.university_tree, leaf_owner, &metadata, nonce, proof)?;
+    }
+
+    // --- Persist our record ---
+    if let (Some(claim), Some(escrow)) = (&args.claim, &mut ctx.accounts.certificate_claim) {
+        escrow.university = ctx.accounts.university.key();
+        escrow.authority = ctx.accounts.university_authority.key();
+        escrow.asset_id = args.asset_id;
+        escrow.merkle_tree = ctx.accounts.merkle_tree.key();
+        escrow.claim_pubkey = claim.claim_pubkey;
+        escrow.expires_at = claim.expires_at;
+        escrow.lock_on_claim = !claim.transferable;
+        escrow.created_at = now;
+        escrow.bump = Pubkey::find_program_address(
+            &[CERTIFICATE_CLAIM_SEED, args.asset_id.as_ref()],
+            ctx.program_id,
+        )
+        .1;
+    }
 
-    // Invoke CPI
-    cpi.invoke()?;
+    if let Some(consent) = &mut ctx.accounts.mint_consent {
+        consent.asset_id = Some(args.asset_id);
+    }
+
+    if let Some(cohort) = &mut ctx.accounts.cohort {
+        cohort.add(&args.asset_id)?;
+    }
+
+    if let (Some(id), Some(mapping)) = (args.credential_id, &mut ctx.accounts.credential_id_record) {
+        mapping.university = ctx.accounts.university.key();
+        mapping.credential_id = id;
+        mapping.asset_id = args.asset_id;
+        mapping.created_at = now;
+        mapping.bump = Pubkey::find_program_address(
+            &[CREDENTIAL_ID_SEED, ctx.accounts.university.key().as_ref(), id.as_ref()],
+            ctx.program_id,
+        )
+        .1;
+    }
+
+    let rec = &mut ctx.accounts.certificate_record;
+    rec.admin = ctx.accounts.global_config.owner;
+    rec.university = ctx.accounts.university.key();
+    rec.merkle_tree = ctx.accounts.merkle_tree.key();
+    rec.collection = ctx.accounts.core_collection.key();
+    rec.co_issuers = co_issuers;
+    rec.cohort = ctx.accounts.cohort.as_ref().map(|c| c.key());
+    rec.asset_id = args.asset_id;
+    rec.recipient = ctx.accounts.recipient.key();
+    rec.nonce = nonce;
+    rec.is_non_transferable = is_non_transferable;
+    rec.symbol = symbol;
+    rec.seller_fee_basis_points = seller_fee_basis_points;
+    rec.is_mutable = is_mutable;
+    rec.name_uri_hash = credential_hash;
+    rec.metadata_hash = args.metadata_hash;
+    rec.documents = args.documents.clone();
+    rec.metadata_version = 0;
+    rec.metadata_chain = [0u8; 32];
+    rec.status = CertificateStatus::Active;
+    rec.revocation_reason = None;
+    rec.revocation_note = String::new();
+    rec.case_reference_hash = None;
+    rec.revoked_at = 0;
+    rec.supersedes = None;
+    rec.superseded_by = None;
+    rec.valid_from = args.valid_from;
+    rec.valid_until = args.valid_until;
+    rec.renewal_count = 0;
+    rec.created_at = now;
+    rec.bump = ctx.bumps.certificate_record;
 
     // Emit program event
     emit!(CertificateMintedV2 {
@@ -206,8 +457,14 @@ pub fn handler(ctx: Context<MintCertificateV2>, args: MintCertificateArgs) -> Re
         name: args.name,
         uri: args.uri,
         attached_collection: args.attach_collection,
+        asset_id: args.asset_id,
+        non_transferable: is_non_transferable,
+        co_issuers: args.co_issuers,
+        metadata_hash: args.metadata_hash,
+        documents: args.documents,
+        credential_id: args.credential_id,
     });
     
 
     Ok(())
-}
\ No newline at end of file
+}
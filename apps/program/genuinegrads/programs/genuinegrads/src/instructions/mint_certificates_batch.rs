@@ -0,0 +1,362 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+use crate::events::{CertificateBatchMinted, CertificateMintedV2};
+use crate::states::{
+    CertificateRecord, CertificateStatus, Cohort, CollectionPolicy, CredentialId, GlobalConfig,
+    MintConsent, ProgramAllowlist, University, UniversityCollection, UniversityTree, leaf_metadata_settings,
+    CERTIFICATE_RECORD_SEED, COHORT_SEED, COLLECTION_POLICY_SEED, CREDENTIAL_ID_SEED,
+    GLOBAL_CONFIG_SEED,
+    MAX_NAME_LEN, MAX_URI_LEN,
+    PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+};
+
+// Bubblegum + deps
+use mpl_bubblegum::types::{MetadataArgsV2, TokenStandard};
+use crate::bubblegum::TreeCpi;
+
+use crate::utils::{
+    asset_id, certificate_creators, create_pda_account, mpl_core_cpi_signer, name_uri_hash,
+    tree_num_minted,
+};
+
+/// Certificates per batch; bounded by transaction size and compute.
+pub const MAX_BATCH_MINT: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchMintEntry {
+    /// Recipient (student); must match the remaining account at the same position
+    pub recipient: Pubkey,
+    pub name: String,
+    pub uri: String,
+    /// SHA-256 of the metadata JSON served at `uri`
+    pub metadata_hash: [u8; 32],
+    /// sha256 of the backend credential id; a retried batch cannot mint it twice
+    pub credential_id: [u8; 32],
+    /// Locked soulbound (SetNonTransferableV2) right after minting, as in
+    /// mint_certificate_v2; `true` skips the lock
+    pub transferable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintCertificatesBatchArgs {
+    /// Backend batch id (sha256 of the BatchIssuanceJob id), echoed in the summary event
+    pub batch_id: [u8; 32],
+    /// Position of this transaction within the batch job
+    pub chunk_index: u32,
+    /// Proof nodes per soulbound entry (tree depth minus canopy depth)
+    pub proof_len: u8,
+    /// Remaining accounts hold one (recipient, certificate_record PDA, credential_id PDA,
+    /// mint_consent PDA or this program's id) quadruple per entry, followed by `proof_len`
+    /// nodes of the new leaf's proof for each soulbound entry, in entry order
+    pub entries: Vec<BatchMintEntry>,
+}
+
+#[derive(Accounts)]
+pub struct MintCertificatesBatch<'info> {
+    /// University authority is payer & operational authority
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(mut, address = university_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Per-collection policy (symbol, naming/URI rules, mutability), enforced once it exists.
+    /// PDA = ["collection_policy", core_collection]
+    #[account(seeds = [COLLECTION_POLICY_SEED, core_collection.key().as_ref()], bump)]
+    pub collection_policy: UncheckedAccount<'info>,
+
+    /// Graduating cohort every entry joins (optional; must not be sealed)
+    /// PDA = ["cohort", university, programme, year]
+    #[account(
+        mut,
+        seeds = [COHORT_SEED, university.key().as_ref(), cohort.programme.as_bytes(), &cohort.year.to_le_bytes()],
+        bump = cohort.bump
+    )]
+    pub cohort: Option<Account<'info, Cohort>>,
+
+    /// CHECK: MPL Core program, ID checked against the allowlist
+    #[account(executable)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's MPL Core CPI signer PDA, derivation verified below
+    pub mpl_core_cpi_signer: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints several collection-attached certificates in one instruction. Entries can be locked
+/// soulbound and accept a MintConsent; they have no validity window, claim, co-issuers or
+/// documents, so use mint_certificate_v2 for those.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MintCertificatesBatch<'info>>, args: MintCertificatesBatchArgs) -> Result<()> {
+    // --- Governance guards (once for the whole batch) ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require!(
+        !args.entries.is_empty() && args.entries.len() <= MAX_BATCH_MINT,
+        GenuineGradsError::InvalidBatchSize
+    );
+    let locked = args.entries.iter().filter(|e| !e.transferable).count();
+    require!(locked == 0 || args.proof_len > 0, GenuineGradsError::MissingMerkleProof);
+    require!(
+        ctx.remaining_accounts.len() == 4 * args.entries.len() + locked * args.proof_len as usize,
+        GenuineGradsError::MissingRemainingAccounts
+    );
+    let (entry_accounts, mut proofs) = ctx.remaining_accounts.split_at(4 * args.entries.len());
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.mpl_core_program.key(), programs.mpl_core, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.mpl_core_cpi_signer.key(), mpl_core_cpi_signer(&programs.bubblegum), GenuineGradsError::InvalidCoreCpiSigner);
+
+    let first_nonce = tree_num_minted(&ctx.accounts.tree_config)?;
+    let now = Clock::get()?.unix_timestamp;
+    let university_key = ctx.accounts.university.key();
+
+    let authority = &ctx.accounts.university_authority.to_account_info();
+    let system_program = &ctx.accounts.system_program.to_account_info();
+    let mpl_core_program = &ctx.accounts.mpl_core_program.to_account_info();
+    let mpl_core_cpi_signer = &ctx.accounts.mpl_core_cpi_signer.to_account_info();
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority,
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program,
+    };
+
+    let policy = CollectionPolicy::load(&ctx.accounts.collection_policy)?;
+
+    for (i, (entry, accounts)) in args.entries.iter().zip(entry_accounts.chunks(4)).enumerate() {
+        let (recipient, record_info, id_info, consent_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+        // --- Per-entry checks (same rules as mint_certificate_v2) ---
+        require_keys_eq!(recipient.key(), entry.recipient, GenuineGradsError::Unauthorized);
+        require!(!entry.name.is_empty() && entry.name.len() <= MAX_NAME_LEN, GenuineGradsError::InvalidName);
+        require!(!entry.uri.is_empty() && entry.uri.len() <= MAX_URI_LEN, GenuineGradsError::InvalidUri);
+        ctx.accounts.global_config.validate_uri(&entry.uri)?;
+        require!(entry.metadata_hash != [0u8; 32], GenuineGradsError::MissingMetadataHash);
+        require!(entry.credential_id != [0u8; 32], GenuineGradsError::InvalidCredentialId);
+
+        // --- Idempotency: PDA = ["credential_id", university, credential_id] must be new ---
+        let (expected_id, id_bump) = Pubkey::find_program_address(
+            &[CREDENTIAL_ID_SEED, university_key.as_ref(), entry.credential_id.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(id_info.key(), expected_id, GenuineGradsError::InvalidCredentialId);
+        require!(id_info.data_is_empty(), GenuineGradsError::DuplicateCredentialId);
+
+        // Student consent: recipient co-signs, or an unused MintConsent names this credential
+        let credential_hash = name_uri_hash(&entry.name, &entry.uri);
+        let consent = MintConsent::load_unused(consent_info, &university_key, &recipient.key(), &credential_hash)?;
+        if ctx.accounts.university.require_student_consent {
+            require!(
+                recipient.is_signer || consent.is_some(),
+                GenuineGradsError::StudentConsentRequired
+            );
+        }
+        let (symbol, seller_fee_basis_points, is_mutable) = leaf_metadata_settings(
+            policy.as_ref(),
+            &entry.name,
+            &entry.uri,
+        )?;
+
+        let nonce = first_nonce + i as u64;
+        let asset_id = asset_id(&programs.bubblegum, ctx.accounts.merkle_tree.key, nonce);
+
+        // --- CPI: MintV2 ---
+        let metadata = MetadataArgsV2 {
+            name: entry.name.clone(),
+            symbol: symbol.clone(),
+            uri: entry.uri.clone(),
+            seller_fee_basis_points,
+            primary_sale_happened: false,
+            is_mutable,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: Some(ctx.accounts.core_collection.key()),
+            creators: certificate_creators(ctx.accounts.university_authority.key(), &[]),
+        };
+        tree.mint(mpl_core_program, mpl_core_cpi_signer, recipient, metadata.clone(), &[])?;
+
+        // --- CPI: SetNonTransferableV2 (soulbound), before the next entry changes the root ---
+        let is_non_transferable = !entry.transferable;
+        if is_non_transferable {
+            let (proof, rest) = proofs.split_at(args.proof_len as usize);
+            proofs = rest;
+            tree.set_non_transferable(&ctx.accounts.university_tree, recipient, &metadata, nonce, proof)?;
+        }
+
+        // --- Create the record PDA = ["certificate", asset_id] ---
+        let (expected_record, bump) = Pubkey::find_program_address(
+            &[CERTIFICATE_RECORD_SEED, asset_id.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(record_info.key(), expected_record, GenuineGradsError::AssetIdMismatch);
+        create_pda_account(
+            authority,
+            record_info,
+            system_program,
+            8 + CertificateRecord::INIT_SPACE,
+            &[CERTIFICATE_RECORD_SEED, asset_id.as_ref(), &[bump]],
+        )?;
+
+        let record = CertificateRecord {
+            admin: ctx.accounts.global_config.owner,
+            university: ctx.accounts.university.key(),
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            collection: ctx.accounts.core_collection.key(),
+            co_issuers: Vec::new(),
+            cohort: ctx.accounts.cohort.as_ref().map(|c| c.key()),
+            asset_id,
+            recipient: recipient.key(),
+            nonce,
+            is_non_transferable,
+            symbol,
+            seller_fee_basis_points,
+            is_mutable,
+            name_uri_hash: credential_hash,
+            metadata_hash: entry.metadata_hash,
+            documents: Vec::new(),
+            metadata_version: 0,
+            metadata_chain: [0u8; 32],
+            status: CertificateStatus::Active,
+            revocation_reason: None,
+            revocation_note: String::new(),
+            case_reference_hash: None,
+            revoked_at: 0,
+            supersedes: None,
+            superseded_by: None,
+            valid_from: None,
+            valid_until: None,
+            renewal_count: 0,
+            created_at: now,
+            bump,
+        };
+        record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        create_pda_account(
+            authority,
+            id_info,
+            system_program,
+            8 + CredentialId::INIT_SPACE,
+            &[CREDENTIAL_ID_SEED, university_key.as_ref(), entry.credential_id.as_ref(), &[id_bump]],
+        )?;
+        let mapping = CredentialId {
+            university: university_key,
+            credential_id: entry.credential_id,
+            asset_id,
+            created_at: now,
+            bump: id_bump,
+        };
+        mapping.try_serialize(&mut &mut id_info.try_borrow_mut_data()?[..])?;
+
+        if let Some(mut consent) = consent {
+            consent.asset_id = Some(asset_id);
+            consent.try_serialize(&mut &mut consent_info.try_borrow_mut_data()?[..])?;
+        }
+
+        if let Some(cohort) = &mut ctx.accounts.cohort {
+            cohort.add(&asset_id)?;
+        }
+
+        emit!(CertificateMintedV2 {
+            admin: ctx.accounts.global_config.owner,
+            university: ctx.accounts.university.key(),
+            authority: ctx.accounts.university_authority.key(),
+            recipient: recipient.key(),
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            tree_config: ctx.accounts.tree_config.key(),
+            collection: ctx.accounts.core_collection.key(),
+            name: entry.name.clone(),
+            uri: entry.uri.clone(),
+            attached_collection: true,
+            asset_id,
+            non_transferable: is_non_transferable,
+            co_issuers: Vec::new(),
+            metadata_hash: entry.metadata_hash,
+            documents: Vec::new(),
+            credential_id: Some(entry.credential_id),
+        });
+    }
+
+    emit!(CertificateBatchMinted {
+        university: ctx.accounts.university.key(),
+        authority: ctx.accounts.university_authority.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        batch_id: args.batch_id,
+        chunk_index: args.chunk_index,
+        first_nonce,
+        count: args.entries.len() as u32,
+        minted_at: now,
+    });
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_config;
 pub mod register_university;
 pub mod approve_university;
@@ -6,6 +8,47 @@ pub mod create_core_collection_v2_cpi;
 pub mod create_tree_v2;
 pub mod mint_certificate_v2;
 pub mod burn_certificate_v2;
+pub mod set_non_transferable_v2;
+pub mod revoke_certificate;
+pub mod reinstate_certificate;
+pub mod init_revocation_list;
+pub mod check_revocation_status;
+pub mod initialize_program_allowlist;
+pub mod propose_program_allowlist_update;
+pub mod apply_program_allowlist_update;
+pub mod reissue_certificate;
+pub mod update_certificate_metadata;
+pub mod verify_certificate_validity;
+pub mod renew_certificate;
+pub mod verify_certificate;
+pub mod claim_certificate;
+pub mod reclaim_certificate;
+pub mod request_certificate_recovery;
+pub mod cancel_certificate_recovery;
+pub mod recover_certificate;
+pub mod set_student_consent_policy;
+pub mod give_mint_consent;
+pub mod withdraw_mint_consent;
+pub mod approve_co_issuance;
+pub mod set_collection_issuance_policy;
+pub mod set_allowed_uri_schemes;
+pub mod verify_document_hash;
+pub mod mint_certificates_batch;
+pub mod create_cohort;
+pub mod seal_cohort;
+pub mod anchor_credential_batch;
+pub mod verify_anchored_credential;
+pub mod revoke_credential_batch;
+pub mod verify_achievement_proof;
+pub mod create_verifying_key;
+pub mod write_verifying_key_chunk;
+pub mod finalize_verifying_key;
+pub mod deprecate_verifying_key;
+pub mod register_achievement_commitment;
+pub mod create_proof_request;
+pub mod migrate_recovery_delegate;
+pub mod migrate_university;
+pub mod migrate_global_config;
 
 pub use initialize_config::*;
 pub use register_university::*;
@@ -15,3 +58,44 @@ pub use create_core_collection_v2_cpi::*;
 pub use create_tree_v2::*;
 pub use mint_certificate_v2::*;
 pub use burn_certificate_v2::*;
+pub use set_non_transferable_v2::*;
+pub use revoke_certificate::*;
+pub use reinstate_certificate::*;
+pub use init_revocation_list::*;
+pub use check_revocation_status::*;
+pub use initialize_program_allowlist::*;
+pub use propose_program_allowlist_update::*;
+pub use apply_program_allowlist_update::*;
+pub use reissue_certificate::*;
+pub use update_certificate_metadata::*;
+pub use verify_certificate_validity::*;
+pub use renew_certificate::*;
+pub use verify_certificate::*;
+pub use claim_certificate::*;
+pub use reclaim_certificate::*;
+pub use request_certificate_recovery::*;
+pub use cancel_certificate_recovery::*;
+pub use recover_certificate::*;
+pub use set_student_consent_policy::*;
+pub use give_mint_consent::*;
+pub use withdraw_mint_consent::*;
+pub use approve_co_issuance::*;
+pub use set_collection_issuance_policy::*;
+pub use set_allowed_uri_schemes::*;
+pub use verify_document_hash::*;
+pub use mint_certificates_batch::*;
+pub use create_cohort::*;
+pub use seal_cohort::*;
+pub use anchor_credential_batch::*;
+pub use verify_anchored_credential::*;
+pub use revoke_credential_batch::*;
+pub use verify_achievement_proof::*;
+pub use create_verifying_key::*;
+pub use write_verifying_key_chunk::*;
+pub use finalize_verifying_key::*;
+pub use deprecate_verifying_key::*;
+pub use register_achievement_commitment::*;
+pub use create_proof_request::*;
+pub use migrate_recovery_delegate::*;
+pub use migrate_university::*;
+pub use migrate_global_config::*;
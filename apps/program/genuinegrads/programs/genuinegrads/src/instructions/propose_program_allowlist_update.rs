@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    ExternalProgramIds, GlobalConfig, ProgramAllowlist, GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED,
+    PROGRAM_ALLOWLIST_TIMELOCK_SECS,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::ProgramAllowlistUpdateProposed;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposeProgramAllowlistUpdateArgs {
+    /// Replacement Bubblegum, MPL Core, account compression and Noop IDs
+    pub programs: ExternalProgramIds,
+}
+
+#[derive(Accounts)]
+pub struct ProposeProgramAllowlistUpdate<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        mut,
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+}
+
+pub fn handler(ctx: Context<ProposeProgramAllowlistUpdate>, args: ProposeProgramAllowlistUpdateArgs) -> Result<()> {
+    let gc = &ctx.accounts.global_config;
+    require_keys_eq!(ctx.accounts.super_admin.key(), gc.owner, GenuineGradsError::Unauthorized);
+
+    let now = Clock::get()?.unix_timestamp;
+    let list = &mut ctx.accounts.program_allowlist;
+
+    // A new proposal replaces any pending one and restarts the timelock
+    list.pending = Some(args.programs);
+    list.pending_eta = now
+        .checked_add(PROGRAM_ALLOWLIST_TIMELOCK_SECS)
+        .ok_or(GenuineGradsError::MathOverflow)?;
+
+    emit!(ProgramAllowlistUpdateProposed {
+        admin: list.admin,
+        program_allowlist: list.key(),
+        current_version: list.version,
+        programs: args.programs,
+        eta: list.pending_eta,
+    });
+
+    Ok(())
+}
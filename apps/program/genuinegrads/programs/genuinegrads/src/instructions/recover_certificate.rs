@@ -0,0 +1,205 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateRecovered;
+use crate::states::{
+    CertificateRecord, CertificateStatus, GlobalConfig, ProgramAllowlist, RecoveryRequest,
+    University, UniversityCollection, UniversityTree, CERTIFICATE_RECORD_SEED, GLOBAL_CONFIG_SEED,
+    PROGRAM_ALLOWLIST_SEED, RECOVERY_DELEGATE_SEED, RECOVERY_REQUEST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED,
+    UNIVERSITY_TREE_SEED,
+};
+
+use crate::bubblegum::{LeafProof, TreeCpi};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecoverCertificateArgs {
+    /// Merkle proof verification (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+
+    /// Optional Bubblegum v2 extras (current leaf flags, e.g. non-transferable)
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverCertificate<'info> {
+    /// University authority is payer; the recovery delegate PDA moves the leaf
+    #[account(mut, address = recovery_request.authority @ GenuineGradsError::Unauthorized)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(mut, address = certificate_record.merkle_tree @ GenuineGradsError::TreeMismatch)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// CHECK: program PDA holding the collection's PermanentTransferDelegate; signs the transfer
+    /// PDA = ["recovery_delegate", core_collection]
+    #[account(seeds = [RECOVERY_DELEGATE_SEED, core_collection.key().as_ref()], bump)]
+    pub recovery_delegate: UncheckedAccount<'info>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, recovery_request.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// Timelocked request, closed once executed
+    /// PDA = ["recovery", asset_id]
+    #[account(
+        mut,
+        seeds = [RECOVERY_REQUEST_SEED, recovery_request.asset_id.as_ref()],
+        bump = recovery_request.bump,
+        close = university_authority
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// CHECK: current (lost) owner – does NOT sign; the permanent transfer delegate acts
+    #[account(address = recovery_request.old_owner @ GenuineGradsError::Unauthorized)]
+    pub leaf_owner: UncheckedAccount<'info>,
+
+    /// CHECK: recovery target wallet
+    #[account(address = recovery_request.new_owner @ GenuineGradsError::InvalidRecoveryTarget)]
+    pub new_leaf_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RecoverCertificate<'info>>, args: RecoverCertificateArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+
+    // --- Timelock: the owner had until eta to cancel ---
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.recovery_request.eta, GenuineGradsError::TimelockNotElapsed);
+
+    // Owner must not have changed since the request, and the leaf must still be valid
+    let rec = &ctx.accounts.certificate_record;
+    require_keys_eq!(rec.recipient, ctx.accounts.recovery_request.old_owner, GenuineGradsError::Unauthorized);
+    require!(rec.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+    require!(args.nonce == rec.nonce && args.index as u64 == rec.nonce, GenuineGradsError::AssetIdMismatch);
+    require!(!ctx.remaining_accounts.is_empty(), GenuineGradsError::MissingMerkleProof);
+
+    // --- CPI: TransferV2 by the collection's PermanentTransferDelegate (our PDA) ---
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf = LeafProof {
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    let core_collection_key = ctx.accounts.core_collection.key();
+    let signer_seeds: &[&[u8]] = &[
+        RECOVERY_DELEGATE_SEED,
+        core_collection_key.as_ref(),
+        &[ctx.bumps.recovery_delegate],
+    ];
+    tree.transfer_signed(
+        &ctx.accounts.recovery_delegate.to_account_info(),
+        signer_seeds,
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &ctx.accounts.new_leaf_owner.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    // --- Record the new owner (request is closed by Anchor) ---
+    let rec = &mut ctx.accounts.certificate_record;
+    rec.recipient = ctx.accounts.new_leaf_owner.key();
+
+    emit!(CertificateRecovered {
+        university: rec.university,
+        authority: ctx.accounts.university_authority.key(),
+        asset_id: rec.asset_id,
+        old_owner: ctx.accounts.leaf_owner.key(),
+        new_owner: rec.recipient,
+        recovered_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    AchievementCommitment, CertificateRecord, CertificateStatus, GlobalConfig, University,
+    ACHIEVEMENT_COMMITMENT_SEED, CERTIFICATE_RECORD_SEED, GLOBAL_CONFIG_SEED, UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::AchievementCommitmentRegistered;
+use crate::groth16::is_field_element;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegisterAchievementCommitmentArgs {
+    /// Poseidon commitment computed by the student, big-endian (part of the PDA)
+    pub commitment: [u8; 32],
+    /// sha256(achievement code) mod r, big-endian
+    pub achievement_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: RegisterAchievementCommitmentArgs)]
+pub struct RegisterAchievementCommitment<'info> {
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// PDA = ["achievement_commitment", asset_id, commitment]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + AchievementCommitment::INIT_SPACE,
+        seeds = [ACHIEVEMENT_COMMITMENT_SEED, certificate_record.asset_id.as_ref(), args.commitment.as_ref()],
+        bump
+    )]
+    pub achievement_commitment: Account<'info, AchievementCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records that the university issued this achievement on the certificate. The
+/// commitment's preimage stays with the student; proofs show they know it.
+pub fn handler(ctx: Context<RegisterAchievementCommitment>, args: RegisterAchievementCommitmentArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require!(
+        ctx.accounts.certificate_record.status == CertificateStatus::Active,
+        GenuineGradsError::AlreadyRevoked
+    );
+    require!(
+        is_field_element(&args.commitment) && is_field_element(&args.achievement_hash),
+        GenuineGradsError::InvalidPublicInput
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let record = &ctx.accounts.certificate_record;
+    let entry = &mut ctx.accounts.achievement_commitment;
+    entry.university = ctx.accounts.university.key();
+    entry.certificate_record = record.key();
+    entry.asset_id = record.asset_id;
+    entry.commitment = args.commitment;
+    entry.achievement_hash = args.achievement_hash;
+    entry.registered_at = now;
+    entry.bump = ctx.bumps.achievement_commitment;
+
+    emit!(AchievementCommitmentRegistered {
+        university: entry.university,
+        asset_id: entry.asset_id,
+        achievement_commitment: entry.key(),
+        commitment: entry.commitment,
+        achievement_hash: entry.achievement_hash,
+        registered_at: now,
+    });
+
+    Ok(())
+}
@@ -58,6 +58,7 @@ pub fn handler(ctx: Context<RegisterUniversity>, args: RegisterUniversityArgs) -
     uni.name = args.name;
     uni.metadata_uri = args.metadata_uri.unwrap_or_default();
     uni.is_active = false; // will be approved by super admin later
+    uni.require_student_consent = false;
     uni.created_at = now;
     uni.bump = bump;
 
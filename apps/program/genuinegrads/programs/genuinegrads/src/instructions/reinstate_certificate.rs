@@ -0,0 +1,221 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateReinstated;
+use crate::states::{
+    CertificateRecord, CertificateStatus, Cohort, GlobalConfig, ProgramAllowlist, RevocationList,
+    University, UniversityCollection, UniversityTree, check_record_cohort, CERTIFICATE_RECORD_SEED,
+    GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED,
+    UNIVERSITY_TREE_SEED,
+};
+
+use crate::bubblegum::{LeafProof, TreeCpi};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReinstateCertificateArgs {
+    /// Merkle proof verification (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+
+    /// Optional Bubblegum v2 extras (current leaf flags, including the frozen bit)
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+}
+
+#[derive(Accounts)]
+pub struct ReinstateCertificate<'info> {
+    /// University authority is payer & PermanentFreezeDelegate of the collection
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    /// Super admin co-signer, required when the stored reason is `LegalOrder` / `IssuerClosure`
+    #[account(address = global_config.owner @ GenuineGradsError::Unauthorized)]
+    pub super_admin: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(mut, address = university_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized,
+        constraint = certificate_record.merkle_tree == merkle_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// Revocation bitmap shard covering this leaf
+    /// PDA = ["revocation_list", university_tree, nonce / 16384]
+    #[account(
+        mut,
+        constraint = revocation_list.load()?.university_tree == university_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+
+    /// Cohort named by the certificate record; required when it has one
+    #[account(mut)]
+    pub cohort: Option<Account<'info, Cohort>>,
+
+    /// CHECK: current leaf owner – does NOT sign; the permanent freeze delegate acts.
+    /// Bubblegum verifies it against the leaf. Must sign only when the stored reason is `StudentRequest`.
+    pub leaf_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ReinstateCertificate<'info>>, args: ReinstateCertificateArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+
+    // Leaf must be the one this record tracks, and currently revoked
+    let rec = &ctx.accounts.certificate_record;
+    require!(args.nonce == rec.nonce && args.index as u64 == rec.nonce, GenuineGradsError::AssetIdMismatch);
+    require!(rec.status == CertificateStatus::Revoked, GenuineGradsError::NotRevoked);
+    require!(rec.superseded_by.is_none(), GenuineGradsError::CertificateSuperseded);
+    // Undoing a revocation needs the same co-signer as making it
+    rec.revocation_reason
+        .ok_or(GenuineGradsError::NotRevoked)?
+        .validate_caller(ctx.accounts.leaf_owner.is_signer, ctx.accounts.super_admin.is_some())?;
+    check_record_cohort(rec.cohort, ctx.accounts.cohort.as_ref())?;
+
+    require!(
+        ctx.accounts.revocation_list.load()?.shard_index == RevocationList::shard_for(args.nonce),
+        GenuineGradsError::RevocationShardMismatch
+    );
+
+    require!(!ctx.remaining_accounts.is_empty(), GenuineGradsError::MissingMerkleProof);
+
+    // --- CPI: ThawV2 ---
+    // Authority is the collection's PermanentFreezeDelegate (its update authority),
+    // so the student does not need to sign.
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf = LeafProof {
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    tree.thaw(&ctx.accounts.leaf_owner.to_account_info(), &leaf, ctx.remaining_accounts)?;
+
+    // --- Clear revocation ---
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut list = ctx.accounts.revocation_list.load_mut()?;
+    list.set_revoked(args.nonce, false);
+    list.updated_at = now;
+    drop(list);
+
+    if let Some(cohort) = &mut ctx.accounts.cohort {
+        cohort.revoked_count = cohort.revoked_count.checked_sub(1).ok_or(GenuineGradsError::MathOverflow)?;
+    }
+
+    let rec = &mut ctx.accounts.certificate_record;
+    let previous_reason = rec.revocation_reason;
+    // Bubblegum verified the current owner as part of the leaf hash
+    rec.recipient = ctx.accounts.leaf_owner.key();
+    rec.status = CertificateStatus::Active;
+    rec.revocation_reason = None;
+    rec.revocation_note = String::new();
+    rec.case_reference_hash = None;
+    rec.revoked_at = 0;
+
+    emit!(CertificateReinstated {
+        admin: rec.admin,
+        university: rec.university,
+        authority: ctx.accounts.university_authority.key(),
+        leaf_owner: rec.recipient,
+        merkle_tree: rec.merkle_tree,
+        collection: rec.collection,
+        asset_id: rec.asset_id,
+        nonce: rec.nonce,
+        previous_reason,
+        reinstated_at: now,
+    });
+
+    Ok(())
+}
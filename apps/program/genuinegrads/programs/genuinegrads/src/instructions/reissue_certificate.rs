@@ -0,0 +1,463 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+
+use crate::errors::GenuineGradsError;
+use crate::events::{CertificateMintedV2, CertificateReissued};
+use crate::states::{
+    CertificateRecord, CertificateStatus, Cohort, CollectionPolicy, CredentialId, DocumentDigest,
+    GlobalConfig, MintConsent, ProgramAllowlist, ReissueMode, RevocationList, RevocationReason,
+    University, UniversityCollection, UniversityTree, check_record_cohort, leaf_metadata_settings,
+    load_co_issuers, validate_documents, validate_revocation_note, CERTIFICATE_RECORD_SEED,
+    COLLECTION_POLICY_SEED, GLOBAL_CONFIG_SEED, MAX_NAME_LEN, MAX_URI_LEN, MINT_CONSENT_SEED,
+    PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+};
+
+// Bubblegum v2 CPI
+use mpl_bubblegum::types::{MetadataArgsV2, TokenStandard};
+use crate::bubblegum::{LeafProof, TreeCpi};
+
+use crate::utils::{
+    asset_id, certificate_creators, mpl_core_cpi_signer, name_uri_hash, tree_num_minted,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReissueCertificateArgs {
+    /// Old leaf proof verification (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+
+    /// Optional Bubblegum v2 extras of the old leaf (current flags, e.g. non-transferable)
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+
+    /// Burn the old leaf or freeze it
+    pub mode: ReissueMode,
+    /// Optional free-text context, e.g. what was corrected (max 120 chars)
+    pub note: Option<String>,
+
+    /// Corrected certificate (same rules as mint_certificate_v2)
+    pub name: String,
+    pub uri: String,
+    /// SHA-256 of the corrected metadata JSON
+    pub metadata_hash: [u8; 32],
+    /// Digests of the corrected documents (PDF diploma, transcript, ...)
+    pub documents: Vec<DocumentDigest>,
+    /// Asset id of the replacement leaf (derived from tree_config.num_minted)
+    pub new_asset_id: Pubkey,
+    /// The replacement is locked soulbound unless set (same default as mint_certificate_v2)
+    pub transferable: bool,
+
+    /// Remaining accounts start with one (University, authority signer or CoIssuanceApproval)
+    /// pair per co-issuer of the old record, as in mint_certificate_v2. Then come
+    /// `old_proof_len` accounts of the old leaf's proof, then the replacement leaf's proof
+    /// (needed unless `transferable`).
+    pub old_proof_len: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ReissueCertificateArgs)]
+pub struct ReissueCertificate<'info> {
+    /// University authority is payer, collection authority and permanent delegate
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree holding both the old and the new leaf
+    #[account(mut, address = university_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Per-collection policy (symbol, naming/URI rules, mutability), enforced once it exists.
+    /// PDA = ["collection_policy", core_collection]
+    #[account(seeds = [COLLECTION_POLICY_SEED, core_collection.key().as_ref()], bump)]
+    pub collection_policy: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program, ID checked against the allowlist
+    #[account(executable)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's MPL Core CPI signer PDA (validated like in mint)
+    pub mpl_core_cpi_signer: UncheckedAccount<'info>,
+
+    /// Record of the certificate being replaced
+    /// PDA = ["certificate", old asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, old_certificate_record.asset_id.as_ref()],
+        bump = old_certificate_record.bump,
+        constraint = old_certificate_record.university == university.key() @ GenuineGradsError::Unauthorized,
+        constraint = old_certificate_record.merkle_tree == merkle_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub old_certificate_record: Account<'info, CertificateRecord>,
+
+    /// Record of the replacement certificate
+    /// PDA = ["certificate", new asset_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + CertificateRecord::INIT_SPACE,
+        seeds = [CERTIFICATE_RECORD_SEED, args.new_asset_id.as_ref()],
+        bump
+    )]
+    pub new_certificate_record: Account<'info, CertificateRecord>,
+
+    /// Revocation bitmap shard covering the old leaf
+    /// PDA = ["revocation_list", university_tree, nonce / 16384]
+    #[account(
+        mut,
+        constraint = revocation_list.load()?.university_tree == university_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+
+    /// Cohort named by the old record; required when it has one.
+    /// The replacement joins it unless it is sealed.
+    #[account(mut)]
+    pub cohort: Option<Account<'info, Cohort>>,
+
+    /// Student's advance consent to the corrected certificate (when the university requires
+    /// consent and the recipient does not co-sign); marked used by this reissue
+    /// PDA = ["mint_consent", university, recipient, credential_hash]
+    #[account(
+        mut,
+        seeds = [
+            MINT_CONSENT_SEED,
+            university.key().as_ref(),
+            recipient.key().as_ref(),
+            mint_consent.credential_hash.as_ref()
+        ],
+        bump = mint_consent.bump
+    )]
+    pub mint_consent: Option<Account<'info, MintConsent>>,
+
+    /// Credential id mapping of the old certificate, if it was minted with one;
+    /// repointed to the replacement
+    /// PDA = ["credential_id", university, credential_id]
+    #[account(
+        mut,
+        constraint = credential_id_record.university == university.key() @ GenuineGradsError::Unauthorized,
+        constraint = credential_id_record.asset_id == old_certificate_record.asset_id @ GenuineGradsError::InvalidCredentialId
+    )]
+    pub credential_id_record: Option<Account<'info, CredentialId>>,
+
+    /// CHECK: owner of the old leaf and recipient of the new one – Bubblegum verifies it
+    /// against the old leaf. Does NOT sign, unless co-signing to give consent.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReissueCertificate<'info>>, args: ReissueCertificateArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    require!(!args.name.is_empty() && args.name.len() <= MAX_NAME_LEN, GenuineGradsError::InvalidName);
+    require!(!args.uri.is_empty() && args.uri.len() <= MAX_URI_LEN, GenuineGradsError::InvalidUri);
+    ctx.accounts.global_config.validate_uri(&args.uri)?;
+    require!(args.metadata_hash != [0u8; 32], GenuineGradsError::MissingMetadataHash);
+    validate_documents(&args.documents)?;
+
+    // Collection issuance policy: symbol, royalty, naming/URI rules and mutability
+    let (symbol, seller_fee_basis_points, is_mutable) = leaf_metadata_settings(
+        CollectionPolicy::load(&ctx.accounts.collection_policy)?.as_ref(),
+        &args.name,
+        &args.uri,
+    )?;
+    validate_revocation_note(&args.note)?;
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.mpl_core_program.key(), programs.mpl_core, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.mpl_core_cpi_signer.key(), mpl_core_cpi_signer(&programs.bubblegum), GenuineGradsError::InvalidCoreCpiSigner);
+
+    // Old leaf must be the one its record tracks, and still valid
+    let old = &ctx.accounts.old_certificate_record;
+    require!(args.nonce == old.nonce && args.index as u64 == old.nonce, GenuineGradsError::AssetIdMismatch);
+    require!(old.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+    require!(old.superseded_by.is_none(), GenuineGradsError::CertificateSuperseded);
+    check_record_cohort(old.cohort, ctx.accounts.cohort.as_ref())?;
+
+    require!(
+        ctx.accounts.revocation_list.load()?.shard_index == RevocationList::shard_for(args.nonce),
+        GenuineGradsError::RevocationShardMismatch
+    );
+
+    // Replacement leaf is appended after the old one is retired; burns don't change num_minted
+    let new_nonce = tree_num_minted(&ctx.accounts.tree_config)?;
+    require_keys_eq!(
+        args.new_asset_id,
+        asset_id(&programs.bubblegum, ctx.accounts.merkle_tree.key, new_nonce),
+        GenuineGradsError::AssetIdMismatch
+    );
+
+    // Unclaimed escrow certificates are reissued once claimed
+    require!(*ctx.accounts.recipient.owner != crate::ID, GenuineGradsError::CertificateInEscrow);
+
+    // Student consent: recipient co-signs, or an unused MintConsent names the correction
+    let credential_hash = name_uri_hash(&args.name, &args.uri);
+    if let Some(consent) = &ctx.accounts.mint_consent {
+        require!(consent.asset_id.is_none(), GenuineGradsError::ConsentAlreadyUsed);
+        require!(consent.credential_hash == credential_hash, GenuineGradsError::StudentConsentRequired);
+    }
+    if ctx.accounts.university.require_student_consent {
+        require!(
+            ctx.accounts.recipient.is_signer || ctx.accounts.mint_consent.is_some(),
+            GenuineGradsError::StudentConsentRequired
+        );
+    }
+
+    // Remaining accounts: [co-issuer pairs..., old leaf proof..., new leaf proof...]
+    let co_issuer_keys: Vec<Pubkey> = old.co_issuers.iter().map(|co| co.university).collect();
+    let old_proof_len = args.old_proof_len as usize;
+    require!(
+        ctx.remaining_accounts.len() >= 2 * co_issuer_keys.len(),
+        GenuineGradsError::MissingRemainingAccounts
+    );
+    let (co_issuer_accounts, proofs) = ctx.remaining_accounts.split_at(2 * co_issuer_keys.len());
+    require!(
+        old_proof_len > 0 && old_proof_len <= proofs.len(),
+        GenuineGradsError::MissingMerkleProof
+    );
+    let (old_proof, new_proof) = proofs.split_at(old_proof_len);
+    require!(args.transferable || !new_proof.is_empty(), GenuineGradsError::MissingMerkleProof);
+
+    // A joint degree keeps its partners; each signs or approves the correction again
+    let co_issuers = load_co_issuers(
+        ctx.program_id,
+        &ctx.accounts.global_config.owner,
+        &ctx.accounts.university.key(),
+        &credential_hash,
+        &args.new_asset_id,
+        &co_issuer_keys,
+        co_issuer_accounts,
+    )?;
+
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let recipient = &ctx.accounts.recipient.to_account_info();
+    let mpl_core_program = &ctx.accounts.mpl_core_program.to_account_info();
+    let mpl_core_cpi_signer = &ctx.accounts.mpl_core_cpi_signer.to_account_info();
+
+    // --- CPI: retire the old leaf (BurnV2 / FreezeV2) ---
+    // The university authority is the collection's permanent burn/freeze delegate,
+    // so the student does not need to sign.
+    let old_leaf = LeafProof {
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    match args.mode {
+        ReissueMode::Burn => tree.burn(mpl_core_program, mpl_core_cpi_signer, recipient, &old_leaf, old_proof)?,
+        ReissueMode::Revoke => tree.freeze(recipient, &old_leaf, old_proof)?,
+    }
+
+    // --- CPI: MintV2 the corrected certificate to the same recipient ---
+    let co_signers: Vec<_> = co_issuer_accounts
+        .chunks(2)
+        .zip(&co_issuers)
+        .filter(|(_, co)| co.verified)
+        .map(|(pair, _)| (&pair[1], false, true))
+        .collect();
+    let metadata = MetadataArgsV2 {
+        name: args.name.clone(),
+        symbol: symbol.clone(),
+        uri: args.uri.clone(),
+        seller_fee_basis_points,
+        primary_sale_happened: false,
+        is_mutable,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: Some(ctx.accounts.core_collection.key()),
+        creators: certificate_creators(ctx.accounts.university_authority.key(), &co_issuers),
+    };
+    tree.mint(mpl_core_program, mpl_core_cpi_signer, recipient, metadata.clone(), &co_signers)?;
+
+    // --- CPI: SetNonTransferableV2 on the replacement (soulbound) ---
+    let is_non_transferable = !args.transferable;
+    if is_non_transferable {
+        tree.set_non_transferable(&ctx.accounts.university_tree, recipient, &metadata, new_nonce, new_proof)?;
+    }
+
+    // --- Link the two records ---
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut list = ctx.accounts.revocation_list.load_mut()?;
+    list.set_revoked(args.nonce, true);
+    list.updated_at = now;
+    drop(list);
+
+    // The original counts as revoked; the correction joins the cohort while it is open
+    let mut new_cohort = None;
+    if let Some(cohort) = &mut ctx.accounts.cohort {
+        cohort.revoked_count = cohort.revoked_count.checked_add(1).ok_or(GenuineGradsError::MathOverflow)?;
+        if !cohort.sealed {
+            cohort.add(&args.new_asset_id)?;
+            new_cohort = Some(cohort.key());
+        }
+    }
+
+    if let Some(consent) = &mut ctx.accounts.mint_consent {
+        consent.asset_id = Some(args.new_asset_id);
+    }
+
+    // Lookups by credential id resolve to the correction
+    if let Some(mapping) = &mut ctx.accounts.credential_id_record {
+        mapping.asset_id = args.new_asset_id;
+    }
+
+    let old = &mut ctx.accounts.old_certificate_record;
+    // Bubblegum verified the current owner as part of the old leaf hash
+    old.recipient = ctx.accounts.recipient.key();
+    old.status = match args.mode {
+        ReissueMode::Burn => CertificateStatus::Burned,
+        ReissueMode::Revoke => CertificateStatus::Revoked,
+    };
+    old.revocation_reason = Some(RevocationReason::Superseded);
+    old.revocation_note = args.note.clone().unwrap_or_default();
+    old.case_reference_hash = None;
+    old.revoked_at = now;
+    old.superseded_by = Some(args.new_asset_id);
+    let old_asset_id = old.asset_id;
+    // The correction inherits the original's validity window
+    let (valid_from, valid_until) = (old.valid_from, old.valid_until);
+
+    let rec = &mut ctx.accounts.new_certificate_record;
+    rec.admin = ctx.accounts.global_config.owner;
+    rec.university = ctx.accounts.university.key();
+    rec.merkle_tree = ctx.accounts.merkle_tree.key();
+    rec.collection = ctx.accounts.core_collection.key();
+    rec.co_issuers = co_issuers.clone();
+    rec.cohort = new_cohort;
+    rec.asset_id = args.new_asset_id;
+    rec.recipient = ctx.accounts.recipient.key();
+    rec.nonce = new_nonce;
+    rec.is_non_transferable = is_non_transferable;
+    rec.symbol = symbol;
+    rec.seller_fee_basis_points = seller_fee_basis_points;
+    rec.is_mutable = is_mutable;
+    rec.name_uri_hash = credential_hash;
+    rec.metadata_hash = args.metadata_hash;
+    rec.documents = args.documents.clone();
+    rec.metadata_version = 0;
+    rec.metadata_chain = [0u8; 32];
+    rec.status = CertificateStatus::Active;
+    rec.revocation_reason = None;
+    rec.revocation_note = String::new();
+    rec.case_reference_hash = None;
+    rec.revoked_at = 0;
+    rec.supersedes = Some(old_asset_id);
+    rec.superseded_by = None;
+    rec.valid_from = valid_from;
+    rec.valid_until = valid_until;
+    rec.renewal_count = 0;
+    rec.created_at = now;
+    rec.bump = ctx.bumps.new_certificate_record;
+
+    emit!(CertificateMintedV2 {
+        admin: ctx.accounts.global_config.owner,
+        university: ctx.accounts.university.key(),
+        authority: ctx.accounts.university_authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        tree_config: ctx.accounts.tree_config.key(),
+        collection: ctx.accounts.core_collection.key(),
+        name: args.name,
+        uri: args.uri,
+        attached_collection: true,
+        asset_id: args.new_asset_id,
+        non_transferable: is_non_transferable,
+        co_issuers: co_issuers.iter().map(|co| co.university).collect(),
+        metadata_hash: args.metadata_hash,
+        documents: args.documents,
+        credential_id: None,
+    });
+
+    emit!(CertificateReissued {
+        admin: ctx.accounts.global_config.owner,
+        university: ctx.accounts.university.key(),
+        authority: ctx.accounts.university_authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        old_asset_id,
+        new_asset_id: args.new_asset_id,
+        mode: args.mode,
+        note: args.note,
+        reissued_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CertificateRecord, CertificateStatus, GlobalConfig, University, CERTIFICATE_RECORD_SEED,
+    GLOBAL_CONFIG_SEED, UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateRenewed;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RenewCertificateArgs {
+    /// New expiry (unix seconds); `None` removes the expiry
+    pub valid_until: Option<i64>,
+}
+
+#[derive(Accounts)]
+pub struct RenewCertificate<'info> {
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+}
+
+/// Extends an expiring certificate in place; the leaf is untouched.
+pub fn handler(ctx: Context<RenewCertificate>, args: RenewCertificateArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let now = Clock::get()?.unix_timestamp;
+    let rec = &mut ctx.accounts.certificate_record;
+    require!(rec.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+
+    // Only credentials with an expiry can be renewed, and only to a later one
+    let previous_valid_until = rec.valid_until.ok_or(GenuineGradsError::InvalidValidityWindow)?;
+    if let Some(valid_until) = args.valid_until {
+        require!(
+            valid_until > previous_valid_until && valid_until > now,
+            GenuineGradsError::InvalidValidityWindow
+        );
+    }
+
+    rec.valid_until = args.valid_until;
+    rec.renewal_count = rec
+        .renewal_count
+        .checked_add(1)
+        .ok_or(GenuineGradsError::MathOverflow)?;
+
+    emit!(CertificateRenewed {
+        university: rec.university,
+        authority: ctx.accounts.university_authority.key(),
+        asset_id: rec.asset_id,
+        previous_valid_until,
+        valid_until: rec.valid_until,
+        renewal_count: rec.renewal_count,
+        renewed_at: now,
+    });
+
+    Ok(())
+}
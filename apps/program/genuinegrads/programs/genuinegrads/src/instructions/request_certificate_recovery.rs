@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CertificateRecord, CertificateStatus, GlobalConfig, RecoveryRequest, University,
+    CERTIFICATE_RECORD_SEED, GLOBAL_CONFIG_SEED, RECOVERY_REQUEST_SEED, RECOVERY_TIMELOCK_SECS,
+    UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateRecoveryRequested;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RequestCertificateRecoveryArgs {
+    /// Wallet the certificate should move to
+    pub new_owner: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct RequestCertificateRecovery<'info> {
+    /// University authority is also the payer
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// One open request per certificate
+    /// PDA = ["recovery", asset_id]
+    #[account(
+        init,
+        payer = university_authority,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+        seeds = [RECOVERY_REQUEST_SEED, certificate_record.asset_id.as_ref()],
+        bump
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RequestCertificateRecovery>, args: RequestCertificateRecoveryArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let rec = &ctx.accounts.certificate_record;
+    require!(rec.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+    require!(
+        args.new_owner != rec.recipient && args.new_owner != Pubkey::default(),
+        GenuineGradsError::InvalidRecoveryTarget
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let req = &mut ctx.accounts.recovery_request;
+    req.university = rec.university;
+    req.authority = ctx.accounts.university_authority.key();
+    req.asset_id = rec.asset_id;
+    req.old_owner = rec.recipient;
+    req.new_owner = args.new_owner;
+    req.requested_at = now;
+    req.eta = now
+        .checked_add(RECOVERY_TIMELOCK_SECS)
+        .ok_or(GenuineGradsError::MathOverflow)?;
+    req.bump = ctx.bumps.recovery_request;
+
+    emit!(CertificateRecoveryRequested {
+        university: req.university,
+        authority: req.authority,
+        asset_id: req.asset_id,
+        old_owner: req.old_owner,
+        new_owner: req.new_owner,
+        eta: req.eta,
+    });
+
+    Ok(())
+}
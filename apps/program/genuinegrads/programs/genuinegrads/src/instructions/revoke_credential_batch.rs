@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CredentialBatch, GlobalConfig, RevocationReason, University, validate_revocation_note,
+    CREDENTIAL_BATCH_SEED, GLOBAL_CONFIG_SEED, UNIVERSITY_SEED,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CredentialBatchRevoked;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevokeCredentialBatchArgs {
+    /// Why every credential in the batch is revoked
+    pub reason: RevocationReason,
+    /// Optional free-text context (max 120 chars)
+    pub note: Option<String>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeCredentialBatch<'info> {
+    pub university_authority: Signer<'info>,
+
+    /// Super admin co-signer, required for `LegalOrder` / `IssuerClosure`
+    #[account(address = global_config.owner @ GenuineGradsError::Unauthorized)]
+    pub super_admin: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["credential_batch", university, batch_id]
+    #[account(
+        mut,
+        seeds = [CREDENTIAL_BATCH_SEED, university.key().as_ref(), credential_batch.batch_id.as_ref()],
+        bump = credential_batch.bump
+    )]
+    pub credential_batch: Account<'info, CredentialBatch>,
+}
+
+pub fn handler(ctx: Context<RevokeCredentialBatch>, args: RevokeCredentialBatchArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+    // A batch has no single holder, so `StudentRequest` is never permitted
+    args.reason.validate_caller(false, ctx.accounts.super_admin.is_some())?;
+    validate_revocation_note(&args.note)?;
+
+    let batch = &mut ctx.accounts.credential_batch;
+    require!(!batch.revoked, GenuineGradsError::AlreadyRevoked);
+
+    let now = Clock::get()?.unix_timestamp;
+    batch.revoked = true;
+    batch.revocation_reason = Some(args.reason);
+    batch.revocation_note = args.note.clone().unwrap_or_default();
+    batch.revoked_at = now;
+
+    emit!(CredentialBatchRevoked {
+        university: batch.university,
+        credential_batch: batch.key(),
+        batch_id: batch.batch_id,
+        reason: args.reason,
+        note: args.note,
+        revoked_at: now,
+    });
+
+    Ok(())
+}
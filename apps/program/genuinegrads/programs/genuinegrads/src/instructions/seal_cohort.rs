@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::states::{Cohort, GlobalConfig, University, COHORT_SEED, GLOBAL_CONFIG_SEED, UNIVERSITY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::CohortSealed;
+
+#[derive(Accounts)]
+pub struct SealCohort<'info> {
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["cohort", university, programme, year]
+    #[account(
+        mut,
+        seeds = [COHORT_SEED, university.key().as_ref(), cohort.programme.as_bytes(), &cohort.year.to_le_bytes()],
+        bump = cohort.bump,
+        constraint = cohort.university == university.key() @ GenuineGradsError::CohortMismatch
+    )]
+    pub cohort: Account<'info, Cohort>,
+}
+
+/// Closes the cohort to new certificates and publishes the Merkle root of its asset ids.
+/// Revocations are still counted after sealing.
+pub fn handler(ctx: Context<SealCohort>) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let cohort = &mut ctx.accounts.cohort;
+    require!(!cohort.sealed, GenuineGradsError::CohortSealed);
+
+    let now = Clock::get()?.unix_timestamp;
+    cohort.root = cohort.current_root();
+    cohort.sealed = true;
+    cohort.sealed_at = now;
+
+    emit!(CohortSealed {
+        university: cohort.university,
+        cohort: cohort.key(),
+        root: cohort.root,
+        issued_count: cohort.issued_count,
+        revoked_count: cohort.revoked_count,
+        expected_count: cohort.expected_count,
+        sealed_at: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::states::{GlobalConfig, GLOBAL_CONFIG_SEED, URI_SCHEME_ALL};
+use crate::errors::GenuineGradsError;
+use crate::events::AllowedUriSchemesUpdated;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetAllowedUriSchemesArgs {
+    /// Bitmask of URI_SCHEME_* (ipfs = 1, ar = 2, https = 4); at least one
+    pub allowed_uri_schemes: u8,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedUriSchemes<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["global-config", super_admin]
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED, super_admin.key().as_ref()],
+        bump = global_config.bump,
+        constraint = global_config.owner == super_admin.key() @ GenuineGradsError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<SetAllowedUriSchemes>, args: SetAllowedUriSchemesArgs) -> Result<()> {
+    require!(
+        args.allowed_uri_schemes != 0 && args.allowed_uri_schemes & !URI_SCHEME_ALL == 0,
+        GenuineGradsError::InvalidUri
+    );
+
+    let gc = &mut ctx.accounts.global_config;
+    gc.allowed_uri_schemes = args.allowed_uri_schemes;
+
+    emit!(AllowedUriSchemesUpdated {
+        owner: gc.owner,
+        allowed_uri_schemes: gc.allowed_uri_schemes,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::states::{
+    CollectionPolicy, GlobalConfig, University, UniversityCollection, COLLECTION_POLICY_SEED,
+    GLOBAL_CONFIG_SEED, MAX_NAME_LEN, MAX_SYMBOL_LEN, NAME_PREFIX_MAX, UNIVERSITY_SEED,
+    UNIVERSITY_COLLECTION_SEED, URI_SCHEME_ALL,
+};
+use crate::errors::GenuineGradsError;
+use crate::events::CollectionPolicyUpdated;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetCollectionIssuancePolicyArgs {
+    /// Leaf symbol (1-10 chars), e.g. "UOM-BSC"
+    pub symbol: String,
+    /// Royalty in basis points (certificates normally use 0)
+    pub seller_fee_basis_points: u16,
+    /// Required name prefix; empty for none
+    pub name_prefix: String,
+    /// Longest accepted name (at most 32, longer than the prefix)
+    pub name_max_len: u8,
+    /// Bitmask of URI_SCHEME_* (ipfs = 1, ar = 2, https = 4); 0 accepts any
+    pub allowed_uri_schemes: u8,
+    /// Mint future leaves as mutable (name/uri updatable via update_certificate_metadata)
+    pub mutable_metadata: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetCollectionIssuancePolicy<'info> {
+    /// University authority is also the payer
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// PDA = ["collection_policy", core_collection]
+    #[account(
+        init_if_needed,
+        payer = university_authority,
+        space = 8 + CollectionPolicy::INIT_SPACE,
+        seeds = [COLLECTION_POLICY_SEED, core_collection.key().as_ref()],
+        bump
+    )]
+    pub collection_policy: Account<'info, CollectionPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetCollectionIssuancePolicy>, args: SetCollectionIssuancePolicyArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    require!(
+        !args.symbol.is_empty() && args.symbol.len() <= MAX_SYMBOL_LEN,
+        GenuineGradsError::InvalidIssuancePolicy
+    );
+    require!(args.seller_fee_basis_points <= 10_000, GenuineGradsError::InvalidIssuancePolicy);
+    require!(
+        args.name_prefix.len() <= NAME_PREFIX_MAX
+            && args.name_prefix.len() < args.name_max_len as usize
+            && args.name_max_len as usize <= MAX_NAME_LEN,
+        GenuineGradsError::InvalidIssuancePolicy
+    );
+    require!(args.allowed_uri_schemes & !URI_SCHEME_ALL == 0, GenuineGradsError::InvalidIssuancePolicy);
+
+    let now = Clock::get()?.unix_timestamp;
+    let policy = &mut ctx.accounts.collection_policy;
+    policy.university = ctx.accounts.university.key();
+    policy.collection = ctx.accounts.core_collection.key();
+    policy.mutable_metadata = args.mutable_metadata;
+    policy.symbol = args.symbol;
+    policy.seller_fee_basis_points = args.seller_fee_basis_points;
+    policy.name_prefix = args.name_prefix;
+    policy.name_max_len = args.name_max_len;
+    policy.allowed_uri_schemes = args.allowed_uri_schemes;
+    policy.updated_at = now;
+    policy.bump = ctx.bumps.collection_policy;
+
+    emit!(CollectionPolicyUpdated {
+        university: policy.university,
+        collection: policy.collection,
+        mutable_metadata: policy.mutable_metadata,
+        symbol: policy.symbol.clone(),
+        seller_fee_basis_points: policy.seller_fee_basis_points,
+        name_prefix: policy.name_prefix.clone(),
+        name_max_len: policy.name_max_len,
+        allowed_uri_schemes: policy.allowed_uri_schemes,
+        updated_at: now,
+    });
+
+    Ok(())
+}
@@ -1,121 +1,212 @@
+#![allow(deprecated, unexpected_cfgs)]
+
 use anchor_lang::prelude::*;
-use mpl_core::ID as MPL_CORE_ID;
-use mpl_bubblegum::{
-    instructions::SetNonTransferableV2CpiBuilder
-};
-use crate::utils::{MplBubblegum, Noop, SplAccountCompression};
 
 use crate::errors::GenuineGradsError;
 use crate::events::CertificateLockedV2;
-use crate::state::{GlobalConfig, University, UniversityCollection, UniversityTree, CertificateRecord};
+use crate::states::{
+    CertificateRecord, CertificateStatus, GlobalConfig, ProgramAllowlist, University,
+    UniversityCollection, UniversityTree, CERTIFICATE_RECORD_SEED, GLOBAL_CONFIG_SEED,
+    PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+};
+
+use crate::bubblegum::{LeafProof, TreeCpi};
 
+use crate::utils::asset_id;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SetNonTransferableArgs {
+    /// Merkle proof verification (from DAS getAssetWithProof)
     pub root: [u8; 32],
     pub data_hash: [u8; 32],
     pub creator_hash: [u8; 32],
     pub nonce: u64,
     pub index: u32,
+
+    /// Optional Bubblegum v2 extras
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+
+    /// Asset id of the leaf (keys the certificate record)
+    pub asset_id: Pubkey,
 }
 
+/// Locks an already minted leaf (e.g. one issued before soulbound minting).
+/// Leaves minted without a certificate record get one here.
 #[derive(Accounts)]
+#[instruction(args: SetNonTransferableArgs)]
 pub struct SetNonTransferableV2<'info> {
-    /// Payer for CPI fees
+    /// University authority is payer & PermanentFreezeDelegate of the collection
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub university_authority: Signer<'info>,
 
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
     #[account(
-        constraint = university.admin == global_config.admin
-            @ GenuineGradsError::Unauthorized,
-        constraint = university.is_active @ GenuineGradsError::UniversityInactive
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
     )]
-    pub university: Account<'info, University>,
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
 
-    /// Must be the university authority (PermanentFreezeDelegate via MPL Core plugin)
-    pub university_authority: Signer<'info>,
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
 
-    /// The current owner of the leaf (student)
-    /// CHECK: only used as pubkey passthrough to Bubblegum
-    pub receiver: UncheckedAccount<'info>,
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
 
-    /// Your recorded tree
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
     pub university_tree: Account<'info, UniversityTree>,
 
-    /// CHECK: must match record
+    /// CHECK: SPL-Compression Merkle tree account
     #[account(mut, address = university_tree.merkle_tree)]
     pub merkle_tree: UncheckedAccount<'info>,
 
-    /// CHECK: must match record
+    /// CHECK: Bubblegum tree_config PDA
     #[account(mut, address = university_tree.tree_config)]
     pub tree_config: UncheckedAccount<'info>,
 
-    /// Link to the MPL-Core collection
-    pub university_collection: Account<'info, UniversityCollection>,
-
-    /// CHECK: must be owned by MPL Core
-    #[account(address = university_collection.core_collection)]
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(mut, address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
     pub core_collection: UncheckedAccount<'info>,
 
-    /// We update the same record we created at mint time
-    #[account(mut,
-        constraint = certificate_record.university == university.key(),
-        constraint = certificate_record.merkle_tree == merkle_tree.key(),
-        constraint = certificate_record.receiver == receiver.key(),
+    /// Certificate record, created here for leaves minted before records existed
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        init_if_needed,
+        payer = university_authority,
+        space = 8 + CertificateRecord::INIT_SPACE,
+        seeds = [CERTIFICATE_RECORD_SEED, args.asset_id.as_ref()],
+        bump
     )]
     pub certificate_record: Account<'info, CertificateRecord>,
 
-    // Programs
-    pub bubblegum_program: Program<'info, MplBubblegum>,
-    pub compression_program: Program<'info, SplAccountCompression>,
-    pub log_wrapper: Program<'info, Noop>,
+    /// CHECK: leaf owner (student wallet); Bubblegum verifies it against the leaf
+    pub leaf_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<SetNonTransferableV2>, args: SetNonTransferableArgs) -> Result<()> {
-    // Auth checks
-    require_keys_eq!(
-        ctx.accounts.university_authority.key(),
-        ctx.accounts.university.authority,
-        GenuineGradsError::Unauthorized
-    );
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SetNonTransferableV2<'info>>, args: SetNonTransferableArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+
+    // The record is keyed by the leaf's asset id
     require_keys_eq!(
-        *ctx.accounts.core_collection.owner,
-        MPL_CORE_ID,
-        GenuineGradsError::NotMplCoreOwned
+        args.asset_id,
+        asset_id(&programs.bubblegum, ctx.accounts.merkle_tree.key, args.nonce),
+        GenuineGradsError::AssetIdMismatch
     );
-
-    // CPI: SetNonTransferableV2 (authority must be PermanentFreezeDelegate on collection per Bubblegum V2)
-    SetNonTransferableV2CpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
-        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
-        .tree_config(&ctx.accounts.tree_config.to_account_info())
-        .leaf_owner(&ctx.accounts.receiver.to_account_info())
-        .leaf_delegate(Some(&ctx.accounts.receiver.to_account_info()))
-        .core_collection(&ctx.accounts.core_collection.to_account_info())
-        .payer(&ctx.accounts.payer.to_account_info())
-        .system_program(&ctx.accounts.system_program.to_account_info())
-        .compression_program(&ctx.accounts.compression_program.to_account_info())
-        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
-        .authority(Some(&ctx.accounts.payer.to_account_info()))
-        .root(args.root)
-        .data_hash(args.data_hash)
-        .creator_hash(args.creator_hash)
-        .nonce(args.nonce)
-        .index(args.index)
-        .invoke()?;
-
-    // Mark record locally
+    require!(!ctx.accounts.certificate_record.is_non_transferable, GenuineGradsError::AlreadyNonTransferable);
+
+    require!(!ctx.remaining_accounts.is_empty(), GenuineGradsError::MissingMerkleProof);
+
+    // --- CPI: SetNonTransferableV2 ---
+    // Authority must be the collection's PermanentFreezeDelegate (its update authority).
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf = LeafProof {
+        root: args.root,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    tree.set_leaf_non_transferable(&ctx.accounts.leaf_owner.to_account_info(), &leaf, ctx.remaining_accounts)?;
+
+    // --- Reflect the lock on our record ---
+    let now = Clock::get()?.unix_timestamp;
     let rec = &mut ctx.accounts.certificate_record;
+    if rec.asset_id == Pubkey::default() {
+        // Leaf minted before certificate records existed
+        rec.admin = ctx.accounts.global_config.owner;
+        rec.university = ctx.accounts.university.key();
+        rec.merkle_tree = ctx.accounts.merkle_tree.key();
+        rec.collection = ctx.accounts.core_collection.key();
+        rec.asset_id = args.asset_id;
+        rec.nonce = args.nonce;
+        // Leaves minted before CollectionPolicy existed are immutable; name/uri unknown here
+        rec.is_mutable = false;
+        rec.name_uri_hash = [0u8; 32];
+        rec.metadata_version = 0;
+        rec.metadata_chain = [0u8; 32];
+        rec.status = CertificateStatus::Active;
+        rec.revocation_reason = None;
+        rec.revocation_note = String::new();
+        rec.case_reference_hash = None;
+        rec.revoked_at = 0;
+        rec.supersedes = None;
+        rec.superseded_by = None;
+        rec.valid_from = None;
+        rec.valid_until = None;
+        rec.renewal_count = 0;
+        rec.created_at = now;
+        rec.bump = ctx.bumps.certificate_record;
+    }
+    // Bubblegum verified the current owner as part of the leaf hash
+    rec.recipient = ctx.accounts.leaf_owner.key();
     rec.is_non_transferable = true;
 
     emit!(CertificateLockedV2 {
         admin: rec.admin,
         university: rec.university,
+        authority: ctx.accounts.university_authority.key(),
+        leaf_owner: rec.recipient,
         merkle_tree: rec.merkle_tree,
-        receiver: rec.receiver,
         collection: rec.collection,
+        asset_id: rec.asset_id,
+        nonce: rec.nonce,
+        locked_at: now,
     });
 
     Ok(())
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::states::{GlobalConfig, University, GLOBAL_CONFIG_SEED, UNIVERSITY_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::StudentConsentPolicyUpdated;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetStudentConsentPolicyArgs {
+    /// Require the student's consent for every future mint
+    pub require_student_consent: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetStudentConsentPolicy<'info> {
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// PDA = ["university", university_authority]
+    #[account(
+        mut,
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+}
+
+pub fn handler(ctx: Context<SetStudentConsentPolicy>, args: SetStudentConsentPolicyArgs) -> Result<()> {
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    let uni = &mut ctx.accounts.university;
+    uni.require_student_consent = args.require_student_consent;
+
+    emit!(StudentConsentPolicyUpdated {
+        university: uni.key(),
+        authority: uni.authority,
+        require_student_consent: uni.require_student_consent,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
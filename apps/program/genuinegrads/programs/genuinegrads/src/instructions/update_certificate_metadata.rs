@@ -0,0 +1,255 @@
+#![allow(deprecated, unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::GenuineGradsError;
+use crate::events::CertificateMetadataUpdated;
+use crate::states::{
+    CertificateRecord, CertificateStatus, CollectionPolicy, GlobalConfig, ProgramAllowlist,
+    University, UniversityCollection, UniversityTree, CERTIFICATE_RECORD_SEED,
+    COLLECTION_POLICY_SEED, GLOBAL_CONFIG_SEED, MAX_NAME_LEN, MAX_URI_LEN,
+    PROGRAM_ALLOWLIST_SEED, UNIVERSITY_SEED, UNIVERSITY_COLLECTION_SEED, UNIVERSITY_TREE_SEED,
+};
+
+use mpl_bubblegum::hash::hash_creators;
+use mpl_bubblegum::types::{MetadataArgsV2, TokenStandard, UpdateArgs};
+
+use crate::bubblegum::{LeafProof, TreeCpi};
+use crate::utils::{certificate_creators, leaf_data_hash, name_uri_hash};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateCertificateMetadataArgs {
+    /// Merkle proof verification (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+
+    /// Optional Bubblegum v2 extras (current leaf flags, e.g. non-transferable)
+    pub asset_data_hash: Option<[u8; 32]>,
+    pub flags: Option<u8>,
+
+    /// Current leaf name/uri (the rest of the metadata is fixed at mint)
+    pub current_name: String,
+    pub current_uri: String,
+
+    /// Only name and uri can change; `None` keeps the current value
+    pub new_name: Option<String>,
+    pub new_uri: Option<String>,
+    /// SHA-256 of the metadata JSON after the update (the JSON embeds the name, so always required)
+    pub new_metadata_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct UpdateCertificateMetadata<'info> {
+    /// University authority is payer & collection update authority
+    #[account(mut)]
+    pub university_authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    /// University must be ACTIVE, tied to this authority & admin
+    /// PDA = ["university", university_authority]
+    #[account(
+        seeds = [UNIVERSITY_SEED, university_authority.key().as_ref()],
+        bump = university.bump,
+        constraint = university.is_active @ GenuineGradsError::UniversityInactive,
+        constraint = university.authority == university_authority.key() @ GenuineGradsError::Unauthorized,
+        constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_collection", university]
+    #[account(
+        seeds = [UNIVERSITY_COLLECTION_SEED, university.key().as_ref()],
+        bump = university_collection.bump
+    )]
+    pub university_collection: Account<'info, UniversityCollection>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(mut, address = university_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree_config PDA
+    #[account(mut, address = university_tree.tree_config)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core collection (must equal record in university_collection)
+    #[account(address = university_collection.collection @ GenuineGradsError::CollectionMismatch)]
+    pub core_collection: UncheckedAccount<'info>,
+
+    /// CHECK: Per-collection policy, enforced once it exists: new name/uri must follow its naming and URI rules.
+    /// PDA = ["collection_policy", core_collection]
+    #[account(seeds = [COLLECTION_POLICY_SEED, core_collection.key().as_ref()], bump)]
+    pub collection_policy: UncheckedAccount<'info>,
+
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        mut,
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump,
+        constraint = certificate_record.university == university.key() @ GenuineGradsError::Unauthorized,
+        constraint = certificate_record.merkle_tree == merkle_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// CHECK: current leaf owner – does NOT sign; the collection authority updates.
+    /// Bubblegum verifies it against the leaf.
+    pub leaf_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program, ID checked against the allowlist
+    #[account(executable)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop log wrapper, ID checked against the allowlist
+    #[account(executable)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UpdateCertificateMetadata<'info>>, args: UpdateCertificateMetadataArgs) -> Result<()> {
+    // --- Governance guards ---
+    require!(!ctx.accounts.global_config.frozen, GenuineGradsError::Frozen);
+
+    // Program IDs (allowlisted in ProgramAllowlist)
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.bubblegum_program.key(), programs.bubblegum, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require_keys_eq!(ctx.accounts.log_wrapper.key(), programs.noop, GenuineGradsError::InvalidProgramExecutable);
+
+    // Leaf must be the one this record tracks, mutable and still valid
+    let rec = &ctx.accounts.certificate_record;
+    require!(args.nonce == rec.nonce && args.index as u64 == rec.nonce, GenuineGradsError::AssetIdMismatch);
+    require!(rec.is_mutable, GenuineGradsError::CertificateImmutable);
+    require!(rec.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+    require!(
+        name_uri_hash(&args.current_name, &args.current_uri) == rec.name_uri_hash,
+        GenuineGradsError::MetadataMismatch
+    );
+
+    // Same limits as mint_certificate_v2
+    require!(args.new_name.is_some() || args.new_uri.is_some(), GenuineGradsError::InvalidName);
+    if let Some(name) = &args.new_name {
+        require!(!name.is_empty() && name.len() <= MAX_NAME_LEN, GenuineGradsError::InvalidName);
+    }
+    if let Some(uri) = &args.new_uri {
+        require!(!uri.is_empty() && uri.len() <= MAX_URI_LEN, GenuineGradsError::InvalidUri);
+        ctx.accounts.global_config.validate_uri(uri)?;
+    }
+    require!(args.new_metadata_hash != [0u8; 32], GenuineGradsError::MissingMetadataHash);
+    if let Some(policy) = CollectionPolicy::load(&ctx.accounts.collection_policy)? {
+        policy.validate_metadata(
+            args.new_name.as_deref().unwrap_or(&args.current_name),
+            args.new_uri.as_deref().unwrap_or(&args.current_uri),
+        )?;
+    }
+
+    require!(!ctx.remaining_accounts.is_empty(), GenuineGradsError::MissingMerkleProof);
+
+    // --- CPI: UpdateMetadataV2 ---
+    // Current metadata is rebuilt from the fixed mint-time fields kept on the record;
+    // Bubblegum re-hashes it against the leaf, so a wrong name/uri fails the proof.
+    let current_metadata = MetadataArgsV2 {
+        name: args.current_name.clone(),
+        symbol: rec.symbol.clone(),
+        uri: args.current_uri.clone(),
+        seller_fee_basis_points: rec.seller_fee_basis_points,
+        primary_sale_happened: false,
+        is_mutable: true,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: Some(ctx.accounts.core_collection.key()),
+        creators: certificate_creators(ctx.accounts.university_authority.key(), &rec.co_issuers),
+    };
+    let update_args = UpdateArgs {
+        name: args.new_name.clone(),
+        symbol: None,
+        uri: args.new_uri.clone(),
+        creators: None,
+        seller_fee_basis_points: None,
+        primary_sale_happened: None,
+        is_mutable: None,
+    };
+
+    let tree = TreeCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        core_collection: &ctx.accounts.core_collection.to_account_info(),
+        authority: &ctx.accounts.university_authority.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let leaf = LeafProof {
+        root: args.root,
+        data_hash: leaf_data_hash(&current_metadata)?,
+        creator_hash: hash_creators(&current_metadata.creators),
+        nonce: args.nonce,
+        index: args.index,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    };
+    tree.update_metadata(
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &leaf,
+        current_metadata,
+        update_args,
+        ctx.remaining_accounts,
+    )?;
+
+    // --- Extend the metadata hash chain ---
+    let name = args.new_name.unwrap_or(args.current_name);
+    let uri = args.new_uri.unwrap_or(args.current_uri);
+
+    let rec = &mut ctx.accounts.certificate_record;
+    let previous_name_uri_hash = rec.name_uri_hash;
+    // Bubblegum verified the current owner as part of the leaf hash
+    rec.recipient = ctx.accounts.leaf_owner.key();
+    rec.metadata_chain = hashv(&[&rec.metadata_chain, &previous_name_uri_hash]).to_bytes();
+    rec.name_uri_hash = name_uri_hash(&name, &uri);
+    rec.metadata_hash = args.new_metadata_hash;
+    rec.metadata_version = rec
+        .metadata_version
+        .checked_add(1)
+        .ok_or(GenuineGradsError::MathOverflow)?;
+
+    emit!(CertificateMetadataUpdated {
+        university: rec.university,
+        authority: ctx.accounts.university_authority.key(),
+        asset_id: rec.asset_id,
+        name,
+        uri,
+        previous_name_uri_hash,
+        metadata_hash: rec.metadata_hash,
+        metadata_chain: rec.metadata_chain,
+        metadata_version: rec.metadata_version,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::errors::GenuineGradsError;
+use crate::events::ZkProofVerified;
+use crate::groth16::{to_field_element, verify_groth16};
+use crate::states::{
+    AchievementCommitment, CertificateRecord, Nullifier, ProofRequest, University, VerifyingKey,
+    ACHIEVEMENT_COMMITMENT_SEED, NULLIFIER_SEED, PROOF_REQUEST_SEED, VERIFYING_KEY_SEED,
+};
+
+/// Circuit id of the zk-service achievement membership circuit (`ach_member_v<version>`).
+pub const ACH_MEMBER_CIRCUIT_ID: &str = "ach_member";
+
+/// Public inputs of `ach_member` from v2 on: [nullifier, commitment, credential_hash,
+/// achievement_hash, request context]. v1 had three and no nullifier, so it is not supported.
+pub const ACH_MEMBER_PUBLIC_INPUTS: u8 = 5;
+
+/// Groth16 proof of the `ach_member` circuit. Points use the alt_bn128 encoding
+/// (big-endian, G2 as x_c1 || x_c0 || y_c1 || y_c0); `proof_a` is passed as snarkjs emits it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifyAchievementProofArgs {
+    /// Circuit version the proof was made for; selects the verifying key
+    pub version: u16,
+    pub proof_a: [u8; 64],
+    pub proof_b: [u8; 128],
+    pub proof_c: [u8; 64],
+    /// Poseidon(credential_hash, student_secret, salt, achievement_hash), big-endian
+    pub commitment: [u8; 32],
+    /// sha256(base58 asset id) mod r, big-endian
+    pub credential_hash: [u8; 32],
+    /// sha256(achievement code) mod r, big-endian
+    pub achievement_hash: [u8; 32],
+    /// Nullifier output of the proof, Poseidon(student_secret, salt, request context), big-endian
+    pub nullifier: [u8; 32],
+}
+
+/// Verifies an achievement membership proof against a proof request; only the verifier
+/// that opened the request can submit it. The commitment must be registered by the
+/// certificate's (active) issuer.
+#[derive(Accounts)]
+#[instruction(args: VerifyAchievementProofArgs)]
+pub struct VerifyAchievementProof<'info> {
+    /// Verifier that opened the proof request; pays for the nullifier record
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    /// PDA = ["verifying_key", admin, "ach_member", version]
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, verifying_key.admin.as_ref(), ACH_MEMBER_CIRCUIT_ID.as_bytes(), &args.version.to_le_bytes()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.finalized @ GenuineGradsError::VerifyingKeyNotFinalized
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+
+    /// PDA = ["achievement_commitment", asset_id, commitment]
+    #[account(
+        seeds = [ACHIEVEMENT_COMMITMENT_SEED, achievement_commitment.asset_id.as_ref(), args.commitment.as_ref()],
+        bump = achievement_commitment.bump
+    )]
+    pub achievement_commitment: Account<'info, AchievementCommitment>,
+
+    #[account(address = achievement_commitment.certificate_record)]
+    pub certificate_record: Account<'info, CertificateRecord>,
+
+    /// Issuer must answer to the admin that published the key
+    #[account(
+        address = achievement_commitment.university,
+        constraint = university.admin == verifying_key.admin @ GenuineGradsError::Unauthorized
+    )]
+    pub university: Account<'info, University>,
+
+    /// Request the proof answers
+    /// PDA = ["proof_request", verifier, nonce]
+    #[account(
+        mut,
+        seeds = [PROOF_REQUEST_SEED, proof_request.verifier.as_ref(), proof_request.nonce.as_ref()],
+        bump = proof_request.bump
+    )]
+    pub proof_request: Account<'info, ProofRequest>,
+
+    /// `init` fails if the nullifier was already used
+    /// PDA = ["nullifier", nullifier]
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + Nullifier::INIT_SPACE,
+        seeds = [NULLIFIER_SEED, args.nullifier.as_ref()],
+        bump
+    )]
+    pub nullifier_record: Account<'info, Nullifier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Fails with `InvalidProof` unless the proof checks out against the referenced key.
+/// Deprecated keys with the current public-input layout still verify, so proofs made
+/// before an upgrade to a later version stay checkable. Keys without that layout (the
+/// old `ach_member_v1`, whose proofs could be replayed indefinitely) are refused with
+/// `InvalidVerifyingKey`.
+pub fn handler(ctx: Context<VerifyAchievementProof>, args: VerifyAchievementProofArgs) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(ctx.accounts.university.is_active, GenuineGradsError::UniversityInactive);
+    let record = &ctx.accounts.certificate_record;
+    record.check_validity(now)?;
+
+    // The public inputs must name this certificate and the registered achievement
+    let entry = &ctx.accounts.achievement_commitment;
+    let credential_hash = to_field_element(hash(record.asset_id.to_string().as_bytes()).to_bytes());
+    require!(
+        args.credential_hash == credential_hash && args.achievement_hash == entry.achievement_hash,
+        GenuineGradsError::CommitmentMismatch
+    );
+
+    // The proof request binds the proof to its context and spends a nullifier
+    let request = &ctx.accounts.proof_request;
+    require_keys_eq!(ctx.accounts.verifier.key(), request.verifier, GenuineGradsError::InvalidProofRequest);
+    if let Some(expires_at) = request.expires_at {
+        require!(now < expires_at, GenuineGradsError::ProofRequestExpired);
+    }
+    if let Some(wanted) = request.achievement_hash {
+        require!(wanted == args.achievement_hash, GenuineGradsError::InvalidProofRequest);
+    }
+    let public_inputs = [
+        args.nullifier,
+        args.commitment,
+        args.credential_hash,
+        args.achievement_hash,
+        request.context,
+    ];
+
+    let vk = &ctx.accounts.verifying_key;
+    require!(vk.public_inputs == ACH_MEMBER_PUBLIC_INPUTS, GenuineGradsError::InvalidVerifyingKey);
+    let key = vk.groth16().ok_or(GenuineGradsError::InvalidVerifyingKey)?;
+    let verified = verify_groth16(&key, &args.proof_a, &args.proof_b, &args.proof_c, &public_inputs)?;
+    require!(verified, GenuineGradsError::InvalidProof);
+
+    emit!(ZkProofVerified {
+        verifier: ctx.accounts.verifier.key(),
+        verifying_key: vk.key(),
+        admin: vk.admin,
+        university: entry.university,
+        asset_id: record.asset_id,
+        circuit_id: vk.circuit_id.clone(),
+        version: vk.version,
+        key_deprecated: vk.deprecated,
+        commitment: args.commitment,
+        credential_hash: args.credential_hash,
+        achievement_hash: args.achievement_hash,
+        proof_request: request.key(),
+        nullifier: args.nullifier,
+        verified_at: now,
+    });
+
+    let request = &mut ctx.accounts.proof_request;
+    request.verified_count = request.verified_count.saturating_add(1);
+
+    let used = &mut ctx.accounts.nullifier_record;
+    used.nullifier = args.nullifier;
+    used.proof_request = request.key();
+    used.achievement_commitment = entry.key();
+    used.used_at = now;
+    used.bump = ctx.bumps.nullifier_record;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::states::{CredentialBatch, University, CREDENTIAL_BATCH_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifyAnchoredCredentialArgs {
+    /// Leaf hash of the off-chain credential
+    pub leaf: [u8; 32],
+    /// Position of the leaf in the batch
+    pub index: u32,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Compact verification result, returned via `set_return_data`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AnchoredCredentialVerification {
+    pub credential_batch: Pubkey,
+    pub university: Pubkey,
+    pub schema_id: String,
+    /// Issuing university is still active
+    pub university_active: bool,
+    /// The proof places the leaf under the batch root
+    pub included: bool,
+    /// The batch has been revoked as a whole
+    pub revoked: bool,
+    /// Included, not revoked, issuer active
+    pub valid: bool,
+}
+
+/// Read-only verification; callable by anyone (or via simulation / CPI).
+#[derive(Accounts)]
+pub struct VerifyAnchoredCredential<'info> {
+    /// PDA = ["credential_batch", university, batch_id]
+    #[account(
+        seeds = [CREDENTIAL_BATCH_SEED, university.key().as_ref(), credential_batch.batch_id.as_ref()],
+        bump = credential_batch.bump
+    )]
+    pub credential_batch: Account<'info, CredentialBatch>,
+
+    #[account(address = credential_batch.university)]
+    pub university: Account<'info, University>,
+}
+
+pub fn handler(ctx: Context<VerifyAnchoredCredential>, args: VerifyAnchoredCredentialArgs) -> Result<AnchoredCredentialVerification> {
+    let batch = &ctx.accounts.credential_batch;
+    let university_active = ctx.accounts.university.is_active;
+    let included = batch.includes(&args.leaf, args.index, &args.proof);
+
+    Ok(AnchoredCredentialVerification {
+        credential_batch: batch.key(),
+        university: batch.university,
+        schema_id: batch.schema_id.clone(),
+        university_active,
+        included,
+        revoked: batch.revoked,
+        valid: included && !batch.revoked && university_active,
+    })
+}
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+use crate::errors::GenuineGradsError;
+use crate::states::{
+    CertificateRecord, GlobalConfig, ProgramAllowlist, RevocationList, University, UniversityTree,
+    GLOBAL_CONFIG_SEED, PROGRAM_ALLOWLIST_SEED, UNIVERSITY_TREE_SEED,
+};
+use crate::utils::asset_id;
+
+use mpl_bubblegum::types::LeafSchema;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifyCertificateArgs {
+    /// Leaf data (from DAS getAssetWithProof)
+    pub root: [u8; 32],
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub nonce: u64,
+    pub index: u32,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub collection_hash: [u8; 32],
+    pub asset_data_hash: [u8; 32],
+    pub flags: u8,
+}
+
+/// Compact verification result, returned via `set_return_data`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CertificateVerification {
+    pub asset_id: Pubkey,
+    pub university: Pubkey,
+    pub owner: Pubkey,
+    /// Issuing university is still active
+    pub university_active: bool,
+    /// Every joint-degree co-issuer is still active (true for single-issuer certificates)
+    pub co_issuers_active: bool,
+    /// Leaf is marked in the revocation bitmap (revoked or burned)
+    pub revoked: bool,
+    /// Inside the record's validity window (true for legacy leaves minted before records)
+    pub within_validity: bool,
+    /// Replacement certificate, if this one was reissued
+    pub superseded_by: Option<Pubkey>,
+    /// All of the above hold
+    pub valid: bool,
+}
+
+/// Read-only verification; callable by anyone (or via simulation / CPI).
+/// An invalid proof fails the instruction; every other check is reported in the result.
+/// Remaining accounts: the record's co-issuer University accounts (joint degrees), then the proof.
+#[derive(Accounts)]
+pub struct VerifyCertificate<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED, global_config.owner.as_ref()],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Trusted external program IDs
+    /// PDA = ["program-allowlist", global_config]
+    #[account(
+        seeds = [PROGRAM_ALLOWLIST_SEED, global_config.key().as_ref()],
+        bump = program_allowlist.bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+
+    #[account(constraint = university.admin == global_config.owner @ GenuineGradsError::Unauthorized)]
+    pub university: Account<'info, University>,
+
+    /// PDA = ["university_tree", merkle_tree]
+    #[account(
+        seeds = [UNIVERSITY_TREE_SEED, merkle_tree.key().as_ref()],
+        bump = university_tree.bump,
+        constraint = university_tree.university == university.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub university_tree: Account<'info, UniversityTree>,
+
+    /// CHECK: SPL-Compression Merkle tree account
+    #[account(address = university_tree.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Revocation bitmap shard covering the leaf
+    /// PDA = ["revocation_list", university_tree, nonce / 16384]
+    #[account(
+        constraint = revocation_list.load()?.university_tree == university_tree.key() @ GenuineGradsError::TreeMismatch
+    )]
+    pub revocation_list: AccountLoader<'info, RevocationList>,
+
+    /// CHECK: certificate record PDA = ["certificate", asset_id]; always required.
+    /// Must hold the leaf's record, or be empty for legacy leaves minted before records.
+    pub certificate_record: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, ID checked against the allowlist
+    #[account(executable)]
+    pub compression_program: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, VerifyCertificate<'info>>, args: VerifyCertificateArgs) -> Result<CertificateVerification> {
+    let programs = ctx.accounts.program_allowlist.programs;
+    require_keys_eq!(ctx.accounts.compression_program.key(), programs.account_compression, GenuineGradsError::InvalidProgramExecutable);
+    require!(args.index as u64 == args.nonce, GenuineGradsError::AssetIdMismatch);
+
+    let asset_id = asset_id(&programs.bubblegum, ctx.accounts.merkle_tree.key, args.nonce);
+    let record = CertificateRecord::load_for_asset(&ctx.accounts.certificate_record, &asset_id)?;
+
+    // Joint degrees: co-issuer University accounts precede the proof
+    let co_issuer_count = record.as_ref().map_or(0, |rec| rec.co_issuers.len());
+    require!(ctx.remaining_accounts.len() > co_issuer_count, GenuineGradsError::MissingMerkleProof);
+    let (co_issuer_accounts, proof) = ctx.remaining_accounts.split_at(co_issuer_count);
+
+    // --- Leaf hash (Bubblegum LeafSchemaV2) ---
+    let leaf = LeafSchema::V2 {
+        id: asset_id,
+        owner: args.owner,
+        delegate: args.delegate,
+        nonce: args.nonce,
+        data_hash: args.data_hash,
+        creator_hash: args.creator_hash,
+        collection_hash: args.collection_hash,
+        asset_data_hash: args.asset_data_hash,
+        flags: args.flags,
+    }
+    .hash();
+
+    // --- CPI: account-compression verify_leaf (fails on a bad proof) ---
+    let mut data = hash(b"global:verify_leaf").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&args.root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&args.index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(ctx.accounts.merkle_tree.key(), false)];
+    let mut account_infos = vec![ctx.accounts.merkle_tree.to_account_info()];
+    for node in proof {
+        accounts.push(AccountMeta::new_readonly(node.key(), false));
+        account_infos.push(node.clone());
+    }
+    account_infos.push(ctx.accounts.compression_program.to_account_info());
+
+    invoke(
+        &Instruction { program_id: programs.account_compression, accounts, data },
+        &account_infos,
+    )?;
+
+    // --- Status ---
+    let list = ctx.accounts.revocation_list.load()?;
+    require!(
+        list.shard_index == RevocationList::shard_for(args.nonce),
+        GenuineGradsError::RevocationShardMismatch
+    );
+    let revoked = list.is_revoked(args.nonce);
+
+    let now = Clock::get()?.unix_timestamp;
+    let (within_validity, superseded_by, co_issuers_active) = match &record {
+        Some(rec) => {
+            require_keys_eq!(rec.university, ctx.accounts.university.key(), GenuineGradsError::Unauthorized);
+            (
+                rec.check_validity(now).is_ok(),
+                rec.superseded_by,
+                rec.co_issuers_active(co_issuer_accounts)?,
+            )
+        }
+        // Legacy leaf: the record PDA was proven empty above
+        None => (true, None, true),
+    };
+
+    let university_active = ctx.accounts.university.is_active;
+    Ok(CertificateVerification {
+        asset_id,
+        university: ctx.accounts.university.key(),
+        owner: args.owner,
+        university_active,
+        co_issuers_active,
+        revoked,
+        within_validity,
+        superseded_by,
+        valid: university_active
+            && co_issuers_active
+            && !revoked
+            && within_validity
+            && superseded_by.is_none(),
+    })
+}
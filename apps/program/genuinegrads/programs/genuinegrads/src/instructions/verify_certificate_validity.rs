@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+use crate::states::{CertificateRecord, CERTIFICATE_RECORD_SEED};
+
+/// Read-only validity check; callable by anyone (or via simulation / CPI).
+#[derive(Accounts)]
+pub struct VerifyCertificateValidity<'info> {
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+}
+
+/// Succeeds only for an active certificate inside its `valid_from` / `valid_until` window.
+/// Joint degrees pass their co-issuers' University accounts as remaining accounts; each must be active.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, VerifyCertificateValidity<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let rec = &ctx.accounts.certificate_record;
+    rec.check_validity(now)?;
+    require!(rec.co_issuers_active(ctx.remaining_accounts)?, GenuineGradsError::UniversityInactive);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::states::{CertificateRecord, DocumentKind, CERTIFICATE_RECORD_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifyDocumentHashArgs {
+    pub kind: DocumentKind,
+    /// SHA-256 of the document the verifier holds
+    pub digest: [u8; 32],
+}
+
+/// Read-only document check; callable by anyone (or via simulation / CPI).
+/// Off-chain verifiers can read `documents` directly from the record as well.
+#[derive(Accounts)]
+pub struct VerifyDocumentHash<'info> {
+    /// PDA = ["certificate", asset_id]
+    #[account(
+        seeds = [CERTIFICATE_RECORD_SEED, certificate_record.asset_id.as_ref()],
+        bump = certificate_record.bump
+    )]
+    pub certificate_record: Account<'info, CertificateRecord>,
+}
+
+/// Returns `true` when a document of this kind with this digest is anchored on the certificate.
+/// Says nothing about the certificate's status; pair it with verify_certificate.
+pub fn handler(ctx: Context<VerifyDocumentHash>, args: VerifyDocumentHashArgs) -> Result<bool> {
+    Ok(ctx
+        .accounts
+        .certificate_record
+        .documents
+        .iter()
+        .any(|doc| doc.kind == args.kind && doc.digest == args.digest))
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::states::{MintConsent, MINT_CONSENT_SEED};
+use crate::errors::GenuineGradsError;
+use crate::events::MintConsentWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawMintConsent<'info> {
+    /// Student who gave the consent; receives the rent back
+    #[account(mut)]
+    pub student: Signer<'info>,
+
+    /// Only unused consents can be withdrawn
+    /// PDA = ["mint_consent", university, student, credential_hash]
+    #[account(
+        mut,
+        seeds = [
+            MINT_CONSENT_SEED,
+            mint_consent.university.as_ref(),
+            student.key().as_ref(),
+            mint_consent.credential_hash.as_ref()
+        ],
+        bump = mint_consent.bump,
+        constraint = mint_consent.asset_id.is_none() @ GenuineGradsError::ConsentAlreadyUsed,
+        close = student
+    )]
+    pub mint_consent: Account<'info, MintConsent>,
+}
+
+pub fn handler(ctx: Context<WithdrawMintConsent>) -> Result<()> {
+    let consent = &ctx.accounts.mint_consent;
+
+    emit!(MintConsentWithdrawn {
+        university: consent.university,
+        student: consent.student,
+        credential_hash: consent.credential_hash,
+        withdrawn_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
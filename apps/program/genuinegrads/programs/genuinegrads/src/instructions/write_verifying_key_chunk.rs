@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::states::{VerifyingKey, VERIFYING_KEY_SEED};
+use crate::errors::GenuineGradsError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WriteVerifyingKeyChunkArgs {
+    /// Must equal the bytes written so far; chunks are appended in order
+    pub offset: u32,
+    pub chunk: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct WriteVerifyingKeyChunk<'info> {
+    pub super_admin: Signer<'info>,
+
+    /// PDA = ["verifying_key", super_admin, circuit_id, version]
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, super_admin.key().as_ref(), verifying_key.circuit_id.as_bytes(), &verifying_key.version.to_le_bytes()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.admin == super_admin.key() @ GenuineGradsError::Unauthorized,
+        constraint = !verifying_key.finalized @ GenuineGradsError::VerifyingKeyFinalized
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+}
+
+pub fn handler(ctx: Context<WriteVerifyingKeyChunk>, args: WriteVerifyingKeyChunkArgs) -> Result<()> {
+    let vk = &mut ctx.accounts.verifying_key;
+    require!(
+        args.offset as usize == vk.data.len()
+            && !args.chunk.is_empty()
+            && vk.data.len() + args.chunk.len() <= VerifyingKey::data_len(vk.public_inputs),
+        GenuineGradsError::InvalidVerifyingKey
+    );
+
+    vk.data.extend_from_slice(&args.chunk);
+    Ok(())
+}
@@ -1,8 +1,10 @@
 #![allow(deprecated, unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
+pub mod bubblegum;
 pub mod errors;
 pub mod events;
+pub mod groth16;
 pub mod instructions;
 pub mod utils;
 pub mod states;
@@ -53,8 +55,8 @@ pub mod genuinegrads {
         create_core_collection_v2_cpi::handler(ctx, args)
     }
 
-    pub fn mint_certificate_v2(
-        ctx: Context<MintCertificateV2>,
+    pub fn mint_certificate_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintCertificateV2<'info>>,
         args: MintCertificateArgs,
     ) -> Result<()> {
         mint_certificate_v2::handler(ctx, args)
@@ -67,10 +69,262 @@ pub mod genuinegrads {
         burn_certificate_v2::handler(ctx, args)
     }
 
-    // pub fn set_non_transferable_v2(ctx: Context<SetNonTransferableV2>, args: SetNonTransferableArgs) -> Result<()> {
-    //     set_non_transferable_v2::handler(ctx, args)
-    // }
+    pub fn set_non_transferable_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetNonTransferableV2<'info>>,
+        args: SetNonTransferableArgs,
+    ) -> Result<()> {
+        set_non_transferable_v2::handler(ctx, args)
+    }
+
+    pub fn revoke_certificate<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevokeCertificate<'info>>,
+        args: RevokeCertificateArgs,
+    ) -> Result<()> {
+        revoke_certificate::handler(ctx, args)
+    }
+
+    pub fn reinstate_certificate<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReinstateCertificate<'info>>,
+        args: ReinstateCertificateArgs,
+    ) -> Result<()> {
+        reinstate_certificate::handler(ctx, args)
+    }
+
+    pub fn reissue_certificate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReissueCertificate<'info>>,
+        args: ReissueCertificateArgs,
+    ) -> Result<()> {
+        reissue_certificate::handler(ctx, args)
+    }
+
+    pub fn update_certificate_metadata<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateCertificateMetadata<'info>>,
+        args: UpdateCertificateMetadataArgs,
+    ) -> Result<()> {
+        update_certificate_metadata::handler(ctx, args)
+    }
+
+    pub fn verify_certificate_validity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCertificateValidity<'info>>,
+    ) -> Result<()> {
+        verify_certificate_validity::handler(ctx)
+    }
+
+    pub fn renew_certificate(
+        ctx: Context<RenewCertificate>,
+        args: RenewCertificateArgs,
+    ) -> Result<()> {
+        renew_certificate::handler(ctx, args)
+    }
 
+    pub fn verify_certificate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCertificate<'info>>,
+        args: VerifyCertificateArgs,
+    ) -> Result<CertificateVerification> {
+        verify_certificate::handler(ctx, args)
+    }
+
+    pub fn claim_certificate<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimCertificate<'info>>,
+        args: ClaimCertificateArgs,
+    ) -> Result<()> {
+        claim_certificate::handler(ctx, args)
+    }
+
+    pub fn reclaim_certificate<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReclaimCertificate<'info>>,
+        args: ReclaimCertificateArgs,
+    ) -> Result<()> {
+        reclaim_certificate::handler(ctx, args)
+    }
+
+    pub fn request_certificate_recovery(
+        ctx: Context<RequestCertificateRecovery>,
+        args: RequestCertificateRecoveryArgs,
+    ) -> Result<()> {
+        request_certificate_recovery::handler(ctx, args)
+    }
+
+    pub fn cancel_certificate_recovery(ctx: Context<CancelCertificateRecovery>) -> Result<()> {
+        cancel_certificate_recovery::handler(ctx)
+    }
+
+    pub fn recover_certificate<'info>(
+        ctx: Context<'_, '_, '_, 'info, RecoverCertificate<'info>>,
+        args: RecoverCertificateArgs,
+    ) -> Result<()> {
+        recover_certificate::handler(ctx, args)
+    }
+
+    pub fn set_student_consent_policy(
+        ctx: Context<SetStudentConsentPolicy>,
+        args: SetStudentConsentPolicyArgs,
+    ) -> Result<()> {
+        set_student_consent_policy::handler(ctx, args)
+    }
+
+    pub fn give_mint_consent(
+        ctx: Context<GiveMintConsent>,
+        args: GiveMintConsentArgs,
+    ) -> Result<()> {
+        give_mint_consent::handler(ctx, args)
+    }
+
+    pub fn withdraw_mint_consent(ctx: Context<WithdrawMintConsent>) -> Result<()> {
+        withdraw_mint_consent::handler(ctx)
+    }
+
+    pub fn approve_co_issuance(
+        ctx: Context<ApproveCoIssuance>,
+        args: ApproveCoIssuanceArgs,
+    ) -> Result<()> {
+        approve_co_issuance::handler(ctx, args)
+    }
+
+    pub fn set_collection_issuance_policy(
+        ctx: Context<SetCollectionIssuancePolicy>,
+        args: SetCollectionIssuancePolicyArgs,
+    ) -> Result<()> {
+        set_collection_issuance_policy::handler(ctx, args)
+    }
+
+    pub fn set_allowed_uri_schemes(
+        ctx: Context<SetAllowedUriSchemes>,
+        args: SetAllowedUriSchemesArgs,
+    ) -> Result<()> {
+        set_allowed_uri_schemes::handler(ctx, args)
+    }
+
+    pub fn verify_document_hash(
+        ctx: Context<VerifyDocumentHash>,
+        args: VerifyDocumentHashArgs,
+    ) -> Result<bool> {
+        verify_document_hash::handler(ctx, args)
+    }
+
+    pub fn mint_certificates_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintCertificatesBatch<'info>>,
+        args: MintCertificatesBatchArgs,
+    ) -> Result<()> {
+        mint_certificates_batch::handler(ctx, args)
+    }
+
+    pub fn create_cohort(ctx: Context<CreateCohort>, args: CreateCohortArgs) -> Result<()> {
+        create_cohort::handler(ctx, args)
+    }
+
+    pub fn seal_cohort(ctx: Context<SealCohort>) -> Result<()> {
+        seal_cohort::handler(ctx)
+    }
+
+    pub fn anchor_credential_batch(
+        ctx: Context<AnchorCredentialBatch>,
+        args: AnchorCredentialBatchArgs,
+    ) -> Result<()> {
+        anchor_credential_batch::handler(ctx, args)
+    }
+
+    pub fn verify_anchored_credential(
+        ctx: Context<VerifyAnchoredCredential>,
+        args: VerifyAnchoredCredentialArgs,
+    ) -> Result<AnchoredCredentialVerification> {
+        verify_anchored_credential::handler(ctx, args)
+    }
+
+    pub fn revoke_credential_batch(
+        ctx: Context<RevokeCredentialBatch>,
+        args: RevokeCredentialBatchArgs,
+    ) -> Result<()> {
+        revoke_credential_batch::handler(ctx, args)
+    }
+
+    pub fn verify_achievement_proof(
+        ctx: Context<VerifyAchievementProof>,
+        args: VerifyAchievementProofArgs,
+    ) -> Result<()> {
+        verify_achievement_proof::handler(ctx, args)
+    }
+
+    pub fn create_verifying_key(
+        ctx: Context<CreateVerifyingKey>,
+        args: CreateVerifyingKeyArgs,
+    ) -> Result<()> {
+        create_verifying_key::handler(ctx, args)
+    }
+
+    pub fn write_verifying_key_chunk(
+        ctx: Context<WriteVerifyingKeyChunk>,
+        args: WriteVerifyingKeyChunkArgs,
+    ) -> Result<()> {
+        write_verifying_key_chunk::handler(ctx, args)
+    }
+
+    pub fn finalize_verifying_key(ctx: Context<FinalizeVerifyingKey>) -> Result<()> {
+        finalize_verifying_key::handler(ctx)
+    }
+
+    pub fn deprecate_verifying_key(ctx: Context<DeprecateVerifyingKey>) -> Result<()> {
+        deprecate_verifying_key::handler(ctx)
+    }
+
+    pub fn register_achievement_commitment(
+        ctx: Context<RegisterAchievementCommitment>,
+        args: RegisterAchievementCommitmentArgs,
+    ) -> Result<()> {
+        register_achievement_commitment::handler(ctx, args)
+    }
+
+    pub fn create_proof_request(
+        ctx: Context<CreateProofRequest>,
+        args: CreateProofRequestArgs,
+    ) -> Result<()> {
+        create_proof_request::handler(ctx, args)
+    }
+
+    pub fn migrate_recovery_delegate(ctx: Context<MigrateRecoveryDelegate>) -> Result<()> {
+        migrate_recovery_delegate::handler(ctx)
+    }
+
+    pub fn migrate_university(ctx: Context<MigrateUniversity>) -> Result<()> {
+        migrate_university::handler(ctx)
+    }
+
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        migrate_global_config::handler(ctx)
+    }
+
+    pub fn init_revocation_list(
+        ctx: Context<InitRevocationList>,
+        args: InitRevocationListArgs,
+    ) -> Result<()> {
+        init_revocation_list::handler(ctx, args)
+    }
+
+    pub fn check_revocation_status(
+        ctx: Context<CheckRevocationStatus>,
+        args: CheckRevocationStatusArgs,
+    ) -> Result<bool> {
+        check_revocation_status::handler(ctx, args)
+    }
+
+    pub fn initialize_program_allowlist(
+        ctx: Context<InitializeProgramAllowlist>
+    ) -> Result<()> {
+        initialize_program_allowlist::handler(ctx)
+    }
+
+    pub fn propose_program_allowlist_update(
+        ctx: Context<ProposeProgramAllowlistUpdate>,
+        args: ProposeProgramAllowlistUpdateArgs,
+    ) -> Result<()> {
+        propose_program_allowlist_update::handler(ctx, args)
+    }
+
+    pub fn apply_program_allowlist_update(
+        ctx: Context<ApplyProgramAllowlistUpdate>
+    ) -> Result<()> {
+        apply_program_allowlist_update::handler(ctx)
+    }
 
     // pub fn update_university(
     //     ctx: Context<UpdateUniversity>,
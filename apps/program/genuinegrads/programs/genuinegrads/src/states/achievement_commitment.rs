@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+pub const ACHIEVEMENT_COMMITMENT_SEED: &[u8] = b"achievement_commitment";
+
+/// A Poseidon commitment the issuing university registered for one achievement on one
+/// certificate. Achievement proofs only verify against registered commitments.
+#[account]
+#[derive(InitSpace)]
+pub struct AchievementCommitment {
+    /// University that registered the commitment (the certificate's issuer)
+    pub university: Pubkey,
+
+    /// CertificateRecord the achievement belongs to
+    pub certificate_record: Pubkey,
+    pub asset_id: Pubkey,
+
+    /// Poseidon(credential_hash, student_secret, salt, achievement_hash), big-endian
+    pub commitment: [u8; 32],
+
+    /// sha256(achievement code) mod r, big-endian
+    pub achievement_hash: [u8; 32],
+
+    pub registered_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::GenuineGradsError;
+
+pub const CERTIFICATE_CLAIM_SEED: &[u8] = b"certificate_claim";
+
+/// Escrow for a claimable certificate. The PDA itself owns the leaf until the
+/// graduate claims it (or the university reclaims it after expiry).
+#[account]
+#[derive(InitSpace)]
+pub struct CertificateClaim {
+    /// The University account that issued the certificate
+    pub university: Pubkey,
+
+    /// University authority that paid for this account (receives rent on close)
+    pub authority: Pubkey,
+
+    /// Bubblegum asset id held in escrow
+    pub asset_id: Pubkey,
+
+    /// SPL-Compression Merkle tree holding the leaf
+    pub merkle_tree: Pubkey,
+
+    /// Public key of the one-time claim keypair handed to the graduate; its secret
+    /// key signs `asset_id || claimant` to claim
+    pub claim_pubkey: Pubkey,
+
+    /// After this time the certificate can no longer be claimed, only reclaimed
+    pub expires_at: i64,
+
+    /// claim_certificate makes the leaf soulbound (unless minted with `claim.transferable`)
+    pub lock_on_claim: bool,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl CertificateClaim {
+    /// Message the claim keypair signs: binds the claim to this escrow and to one wallet,
+    /// so a seen or failed claim cannot be replayed with another claimant.
+    pub fn claim_message(&self, claimant: &Pubkey) -> [u8; 64] {
+        let mut message = [0u8; 64];
+        message[..32].copy_from_slice(self.asset_id.as_ref());
+        message[32..].copy_from_slice(claimant.as_ref());
+        message
+    }
+
+    /// Requires the instruction right before the current one to be an Ed25519 program
+    /// instruction carrying one signature by `claim_pubkey` over `claim_message(claimant)`.
+    /// The precompile verified the signature; this checks it signed the right thing.
+    pub fn verify_claimant(&self, instructions_sysvar: &AccountInfo, claimant: &Pubkey) -> Result<()> {
+        let current = load_current_index_checked(instructions_sysvar)?;
+        require!(current > 0, GenuineGradsError::InvalidClaimSignature);
+        let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+        require_keys_eq!(ix.program_id, ed25519_program::ID, GenuineGradsError::InvalidClaimSignature);
+
+        // [num_signatures: u8, padding: u8, offsets: 7 x u16 LE] followed by the data
+        let data = &ix.data;
+        require!(data.len() >= 16 && data[0] == 1, GenuineGradsError::InvalidClaimSignature);
+        let offset = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]);
+        let (signature_ix, pubkey_offset, pubkey_ix) = (offset(1), offset(2) as usize, offset(3));
+        let (message_offset, message_size, message_ix) = (offset(4) as usize, offset(5) as usize, offset(6));
+
+        // Signature, key and message must all live in the Ed25519 instruction itself
+        require!(
+            signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+            GenuineGradsError::InvalidClaimSignature
+        );
+        require!(
+            data.get(pubkey_offset..pubkey_offset + 32) == Some(self.claim_pubkey.as_ref()),
+            GenuineGradsError::InvalidClaimSignature
+        );
+        let message = self.claim_message(claimant);
+        require!(
+            message_size == message.len()
+                && data.get(message_offset..message_offset + message_size) == Some(&message[..]),
+            GenuineGradsError::InvalidClaimSignature
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+use crate::states::{University, MAX_SYMBOL_LEN};
+
+pub const CERTIFICATE_RECORD_SEED: &[u8] = b"certificate";
+
+pub const REVOCATION_NOTE_MAX: usize = 120;
+
+/// Partner universities allowed on one joint degree (besides the minting university).
+pub const MAX_CO_ISSUERS: usize = 4;
+
+/// Document digests anchored on one certificate.
+pub const MAX_DOCUMENTS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CertificateStatus {
+    /// Valid credential
+    Active,
+    /// Leaf frozen by the issuer; can be reinstated
+    Revoked,
+    /// Leaf burned; permanent
+    Burned,
+}
+
+/// Machine-readable reason for revoking a certificate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RevocationReason {
+    AcademicMisconduct,
+    IssuedInError,
+    Superseded,
+    StudentRequest,
+    LegalOrder,
+    IssuerClosure,
+}
+
+/// How `reissue_certificate` retires the original leaf.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ReissueMode {
+    /// BurnV2 via the PermanentBurnDelegate
+    Burn,
+    /// FreezeV2 via the PermanentFreezeDelegate (leaf stays on-chain)
+    Revoke,
+}
+
+/// Partner university of a joint degree, listed as an extra creator on the leaf.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct CoIssuer {
+    /// Partner University account
+    pub university: Pubkey,
+    /// Its authority (the creator address on the leaf)
+    pub authority: Pubkey,
+    /// Creator is verified (authority co-signed the mint) rather than pre-approved
+    pub verified: bool,
+}
+
+/// Kind of off-chain document anchored on a certificate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DocumentKind {
+    /// PDF diploma generated by the backend
+    Diploma,
+    Transcript,
+    Supplementary,
+}
+
+/// SHA-256 of a document issued alongside the certificate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DocumentDigest {
+    pub kind: DocumentKind,
+    pub digest: [u8; 32],
+}
+
+/// At most MAX_DOCUMENTS non-zero, distinct digests.
+pub fn validate_documents(documents: &[DocumentDigest]) -> Result<()> {
+    require!(documents.len() <= MAX_DOCUMENTS, GenuineGradsError::InvalidDocumentDigest);
+    for (i, doc) in documents.iter().enumerate() {
+        require!(
+            doc.digest != [0u8; 32] && !documents[..i].iter().any(|d| d.digest == doc.digest),
+            GenuineGradsError::InvalidDocumentDigest
+        );
+    }
+    Ok(())
+}
+
+impl RevocationReason {
+    /// The university authority always signs; some reasons need a co-signer:
+    /// `StudentRequest` the student (leaf owner), `LegalOrder` / `IssuerClosure` the super admin.
+    pub fn validate_caller(&self, leaf_owner_signed: bool, super_admin_signed: bool) -> Result<()> {
+        let permitted = match self {
+            RevocationReason::AcademicMisconduct
+            | RevocationReason::IssuedInError
+            | RevocationReason::Superseded => true,
+            RevocationReason::StudentRequest => leaf_owner_signed,
+            RevocationReason::LegalOrder | RevocationReason::IssuerClosure => super_admin_signed,
+        };
+        require!(permitted, GenuineGradsError::ReasonNotPermittedForCaller);
+        Ok(())
+    }
+}
+
+/// Optional free-text note accompanying a reason code.
+pub fn validate_revocation_note(note: &Option<String>) -> Result<()> {
+    if let Some(note) = note {
+        require!(
+            !note.trim().is_empty() && note.len() <= REVOCATION_NOTE_MAX,
+            GenuineGradsError::InvalidRevocationNote
+        );
+    }
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CertificateRecord {
+    /// Program-level super admin (mirrors GlobalConfig.owner)
+    pub admin: Pubkey,
+
+    /// The University account that issued this certificate
+    pub university: Pubkey,
+
+    /// SPL-Compression Merkle tree holding the leaf
+    pub merkle_tree: Pubkey,
+
+    /// MPL Core collection the leaf belongs to
+    pub collection: Pubkey,
+
+    /// Partner universities of a joint degree (empty for single-issuer certificates)
+    #[max_len(MAX_CO_ISSUERS)]
+    pub co_issuers: Vec<CoIssuer>,
+
+    /// Graduating cohort the certificate was minted into, if any
+    pub cohort: Option<Pubkey>,
+
+    /// Bubblegum asset id: PDA = ["asset", merkle_tree, nonce] under Bubblegum
+    pub asset_id: Pubkey,
+
+    /// Leaf owner (student wallet)
+    pub recipient: Pubkey,
+
+    /// Leaf nonce (equal to the leaf index in the tree)
+    pub nonce: u64,
+
+    /// Whether the leaf carries Bubblegum's non-transferable (soulbound) flag
+    pub is_non_transferable: bool,
+
+    /// Leaf symbol and royalty fixed at mint (from the collection policy)
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    pub seller_fee_basis_points: u16,
+
+    /// Whether the leaf was minted mutable (name/uri updatable)
+    pub is_mutable: bool,
+    /// utils::name_uri_hash of the leaf's current name and uri
+    pub name_uri_hash: [u8; 32],
+    /// SHA-256 of the current off-chain metadata JSON; verifiers compare the fetched JSON to it
+    pub metadata_hash: [u8; 32],
+
+    /// Digests of the PDF diploma, transcript and supplementary documents
+    #[max_len(MAX_DOCUMENTS)]
+    pub documents: Vec<DocumentDigest>,
+    /// Number of metadata updates so far
+    pub metadata_version: u32,
+    /// Hash chain of prior metadata: chain = sha256(chain || name_uri_hash of replaced metadata)
+    pub metadata_chain: [u8; 32],
+
+    /// Revocation state (Revoked == leaf frozen via the collection's PermanentFreezeDelegate)
+    pub status: CertificateStatus,
+    pub revocation_reason: Option<RevocationReason>,
+    #[max_len(REVOCATION_NOTE_MAX)]
+    pub revocation_note: String,
+    /// Hash of the off-chain case file (e.g. SHA-256 of the misconduct ruling)
+    pub case_reference_hash: Option<[u8; 32]>,
+    pub revoked_at: i64,
+
+    /// Asset id of the certificate this one replaces (set by reissue_certificate)
+    pub supersedes: Option<Pubkey>,
+    /// Asset id of the replacement certificate; verifiers should follow it
+    pub superseded_by: Option<Pubkey>,
+
+    /// Embargo: not valid before this time (e.g. official conferral date)
+    pub valid_from: Option<i64>,
+    /// Expiry for time-limited credentials; extended by renew_certificate
+    pub valid_until: Option<i64>,
+    /// Number of renewals so far
+    pub renewal_count: u32,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl CertificateRecord {
+    /// Fails unless the certificate is active and `now` is inside its validity window.
+    pub fn check_validity(&self, now: i64) -> Result<()> {
+        require!(self.status == CertificateStatus::Active, GenuineGradsError::AlreadyRevoked);
+        if let Some(valid_from) = self.valid_from {
+            require!(now >= valid_from, GenuineGradsError::CertificateNotYetValid);
+        }
+        if let Some(valid_until) = self.valid_until {
+            require!(now < valid_until, GenuineGradsError::CertificateExpired);
+        }
+        Ok(())
+    }
+
+    /// Whether every joint-degree co-issuer is still active. `universities` must start with
+    /// the co-issuers' University accounts, in record order.
+    pub fn co_issuers_active<'info>(&self, universities: &'info [AccountInfo<'info>]) -> Result<bool> {
+        require!(
+            universities.len() >= self.co_issuers.len(),
+            GenuineGradsError::MissingRemainingAccounts
+        );
+        let mut active = true;
+        for (co, info) in self.co_issuers.iter().zip(universities) {
+            require_keys_eq!(info.key(), co.university, GenuineGradsError::InvalidCoIssuer);
+            active &= Account::<University>::try_from(info)?.is_active;
+        }
+        Ok(active)
+    }
+
+    /// Loads the record at `info`, which must be the PDA ["certificate", asset_id].
+    /// Returns `None` only when that PDA holds no data (legacy leaves minted before records).
+    pub fn load_for_asset(info: &AccountInfo, asset_id: &Pubkey) -> Result<Option<Self>> {
+        let (expected, _) =
+            Pubkey::find_program_address(&[CERTIFICATE_RECORD_SEED, asset_id.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected, GenuineGradsError::CertificateRecordMismatch);
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, GenuineGradsError::CertificateRecordMismatch);
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Writes a record loaded with `load_for_asset` back to its account.
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
+
+/// `valid_until` must come after `valid_from` when both are set.
+pub fn validate_validity_window(valid_from: Option<i64>, valid_until: Option<i64>) -> Result<()> {
+    if let (Some(from), Some(until)) = (valid_from, valid_until) {
+        require!(until > from, GenuineGradsError::InvalidValidityWindow);
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+use crate::states::{CoIssuer, University};
+
+pub const CO_ISSUANCE_SEED: &[u8] = b"co_issuance";
+
+/// A partner university's advance approval to appear as co-issuer on one
+/// joint-degree certificate minted by another university.
+#[account]
+#[derive(InitSpace)]
+pub struct CoIssuanceApproval {
+    /// Approving (co-issuing) University account
+    pub university: Pubkey,
+
+    /// University that will mint the certificate
+    pub partner: Pubkey,
+
+    /// name_uri_hash(name, uri) of the approved certificate
+    pub credential_hash: [u8; 32],
+
+    /// Set once the approval has been used by mint_certificate_v2
+    pub asset_id: Option<Pubkey>,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Validates the co-issuers of a joint degree. `accounts` holds one pair per entry of
+/// `co_issuers`: the partner University, then either its authority as a signer (verified
+/// creator) or its unused CoIssuanceApproval for this credential, which gets consumed.
+pub fn load_co_issuers<'info>(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    university: &Pubkey,
+    credential_hash: &[u8; 32],
+    asset_id: &Pubkey,
+    co_issuers: &[Pubkey],
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<CoIssuer>> {
+    let mut loaded: Vec<CoIssuer> = Vec::with_capacity(co_issuers.len());
+    for (key, pair) in co_issuers.iter().zip(accounts.chunks(2)) {
+        require!(
+            key != university && !loaded.iter().any(|co| co.university == *key),
+            GenuineGradsError::InvalidCoIssuer
+        );
+
+        let partner = Account::<University>::try_from(&pair[0])?;
+        require_keys_eq!(partner.key(), *key, GenuineGradsError::InvalidCoIssuer);
+        require_keys_eq!(partner.admin, *admin, GenuineGradsError::Unauthorized);
+        require!(partner.is_active, GenuineGradsError::UniversityInactive);
+
+        let approver = &pair[1];
+        let verified = if approver.is_signer && approver.key() == partner.authority {
+            true
+        } else {
+            // The approval is consumed below; a read-only account would drop that write
+            require!(approver.is_writable, GenuineGradsError::CoIssuanceNotApproved);
+            let mut approval = Account::<CoIssuanceApproval>::try_from(approver)?;
+            require!(
+                approval.university == *key
+                    && approval.partner == *university
+                    && approval.credential_hash == *credential_hash
+                    && approval.asset_id.is_none(),
+                GenuineGradsError::CoIssuanceNotApproved
+            );
+            approval.asset_id = Some(*asset_id);
+            approval.exit(program_id)?;
+            false
+        };
+
+        loaded.push(CoIssuer {
+            university: *key,
+            authority: partner.authority,
+            verified,
+        });
+    }
+    Ok(loaded)
+}
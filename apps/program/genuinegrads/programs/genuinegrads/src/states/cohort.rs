@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::errors::GenuineGradsError;
+
+pub const COHORT_SEED: &[u8] = b"cohort";
+
+/// Programme names are PDA seeds, so at most 32 bytes.
+pub const MAX_PROGRAMME_LEN: usize = 32;
+
+/// Depth of the cohort's incremental Merkle tree over asset ids (up to 65,536 certificates).
+pub const COHORT_TREE_DEPTH: usize = 16;
+
+/// A graduating class: one programme, one year, one conferral date.
+/// Mints that reference it are counted and their asset ids accumulated in an
+/// append-only Merkle tree whose root is published when the cohort is sealed.
+#[account]
+#[derive(InitSpace)]
+pub struct Cohort {
+    /// University that owns the cohort
+    pub university: Pubkey,
+
+    /// Programme name, e.g. "BSc Computer Science"
+    #[max_len(MAX_PROGRAMME_LEN)]
+    pub programme: String,
+
+    /// Graduation year
+    pub year: u16,
+
+    /// Degree conferral date (unix seconds)
+    pub conferral_date: i64,
+
+    /// Graduates the university expects to certify (informational)
+    pub expected_count: u32,
+
+    /// Certificates minted into the cohort
+    pub issued_count: u32,
+
+    /// Cohort certificates currently revoked or burned
+    pub revoked_count: u32,
+
+    /// Sealed cohorts accept no further certificates
+    pub sealed: bool,
+    pub sealed_at: i64,
+
+    /// Merkle root over the issued asset ids, set when sealed
+    pub root: [u8; 32],
+
+    /// Left siblings of the next insertion path (incremental Merkle tree)
+    pub frontier: [[u8; 32]; COHORT_TREE_DEPTH],
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Cohort {
+    /// Appends a newly minted certificate to the cohort.
+    pub fn add(&mut self, asset_id: &Pubkey) -> Result<()> {
+        require!(!self.sealed, GenuineGradsError::CohortSealed);
+        require!(
+            (self.issued_count as u64) < 1u64 << COHORT_TREE_DEPTH,
+            GenuineGradsError::CohortFull
+        );
+
+        let mut node = cohort_leaf(asset_id);
+        let mut size = self.issued_count + 1;
+        for height in 0..COHORT_TREE_DEPTH {
+            if size & 1 == 1 {
+                self.frontier[height] = node;
+                break;
+            }
+            node = hashv(&[&self.frontier[height], &node]).to_bytes();
+            size >>= 1;
+        }
+        self.issued_count += 1;
+        Ok(())
+    }
+
+    /// Root over the asset ids added so far; empty slots are zero subtrees.
+    pub fn current_root(&self) -> [u8; 32] {
+        let mut node = [0u8; 32];
+        let mut zero = [0u8; 32];
+        let mut size = self.issued_count;
+        for height in 0..COHORT_TREE_DEPTH {
+            node = if size & 1 == 1 {
+                hashv(&[&self.frontier[height], &node]).to_bytes()
+            } else {
+                hashv(&[&node, &zero]).to_bytes()
+            };
+            zero = hashv(&[&zero, &zero]).to_bytes();
+            size >>= 1;
+        }
+        node
+    }
+}
+
+/// Leaf of the cohort tree: sha256(asset_id).
+pub fn cohort_leaf(asset_id: &Pubkey) -> [u8; 32] {
+    hashv(&[asset_id.as_ref()]).to_bytes()
+}
+
+/// A certificate's cohort account must be passed whenever its record names one.
+pub fn check_record_cohort(record_cohort: Option<Pubkey>, cohort: Option<&Account<Cohort>>) -> Result<()> {
+    require!(
+        record_cohort == cohort.map(|c| c.key()),
+        GenuineGradsError::CohortMismatch
+    );
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+
+pub const COLLECTION_POLICY_SEED: &[u8] = b"collection_policy";
+
+/// Symbol used when a collection has no policy.
+pub const DEFAULT_CERTIFICATE_SYMBOL: &str = "GG-CERT";
+
+/// Bubblegum limits for leaf metadata.
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_URI_LEN: usize = 200;
+
+pub const NAME_PREFIX_MAX: usize = 16;
+
+/// Metadata URI schemes, combined as a bitmask in `allowed_uri_schemes`.
+pub const URI_SCHEME_IPFS: u8 = 1 << 0;
+pub const URI_SCHEME_AR: u8 = 1 << 1;
+pub const URI_SCHEME_HTTPS: u8 = 1 << 2;
+pub const URI_SCHEME_ALL: u8 = URI_SCHEME_IPFS | URI_SCHEME_AR | URI_SCHEME_HTTPS;
+
+/// Scheme bit of a metadata URI, if it is one we recognise.
+pub fn uri_scheme(uri: &str) -> Option<u8> {
+    if uri.starts_with("ipfs://") {
+        Some(URI_SCHEME_IPFS)
+    } else if uri.starts_with("ar://") {
+        Some(URI_SCHEME_AR)
+    } else if uri.starts_with("https://") {
+        Some(URI_SCHEME_HTTPS)
+    } else {
+        None
+    }
+}
+
+/// Symbol, royalty and mutability for a new leaf, after checking its name and uri
+/// against the collection's policy (if any).
+pub fn leaf_metadata_settings(
+    policy: Option<&CollectionPolicy>,
+    name: &str,
+    uri: &str,
+) -> Result<(String, u16, bool)> {
+    match policy {
+        Some(policy) => {
+            policy.validate_metadata(name, uri)?;
+            Ok((policy.symbol.clone(), policy.seller_fee_basis_points, policy.mutable_metadata))
+        }
+        // Leaves are immutable unless the collection opted in to metadata updates
+        None => Ok((DEFAULT_CERTIFICATE_SYMBOL.to_string(), 0, false)),
+    }
+}
+
+/// Per-collection issuance settings, owned by the university authority.
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionPolicy {
+    /// The University account that owns the collection
+    pub university: Pubkey,
+
+    /// MPL Core collection this policy applies to
+    pub collection: Pubkey,
+
+    /// Mint leaves with `is_mutable = true` so name/uri can later be changed
+    /// through update_certificate_metadata. Only affects leaves minted after opting in.
+    pub mutable_metadata: bool,
+
+    /// Leaf symbol, e.g. "UOM-BSC"
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+
+    /// Royalty written to the leaf, in basis points
+    pub seller_fee_basis_points: u16,
+
+    /// Every certificate name must start with this (empty = any)
+    #[max_len(NAME_PREFIX_MAX)]
+    pub name_prefix: String,
+
+    /// Longest accepted certificate name (at most 32)
+    pub name_max_len: u8,
+
+    /// Bitmask of URI_SCHEME_* accepted for the metadata uri (0 = any)
+    pub allowed_uri_schemes: u8,
+
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl CollectionPolicy {
+    /// The policy stored at a collection's (seed-checked) policy PDA, or `None` if the
+    /// collection has none yet.
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let data = account.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Checks a certificate name and uri against the naming and URI rules.
+    pub fn validate_metadata(&self, name: &str, uri: &str) -> Result<()> {
+        require!(
+            name.len() <= self.name_max_len as usize && name.starts_with(self.name_prefix.as_str()),
+            GenuineGradsError::NameNotAllowedByPolicy
+        );
+        if self.allowed_uri_schemes != 0 {
+            require!(
+                uri_scheme(uri).is_some_and(|scheme| self.allowed_uri_schemes & scheme != 0),
+                GenuineGradsError::UriSchemeNotAllowed
+            );
+        }
+        Ok(())
+    }
+}
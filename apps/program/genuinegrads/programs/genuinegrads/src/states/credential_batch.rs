@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::states::{RevocationReason, REVOCATION_NOTE_MAX};
+
+pub const CREDENTIAL_BATCH_SEED: &[u8] = b"credential_batch";
+
+/// Schema ids name the off-chain credential format, e.g. "course-completion/v1".
+pub const MAX_SCHEMA_ID_LEN: usize = 64;
+
+/// Anchor-only issuance: a Merkle root committing to a batch of off-chain credentials
+/// (micro-credentials, course completions) instead of one cNFT per credential.
+#[account]
+#[derive(InitSpace)]
+pub struct CredentialBatch {
+    /// University that anchored the batch
+    pub university: Pubkey,
+
+    /// Backend batch id (sha256 of the job id)
+    pub batch_id: [u8; 32],
+
+    /// Merkle root over the credential leaf hashes, padded with zero leaves
+    /// to the next power of two; parent = sha256(left || right)
+    pub root: [u8; 32],
+
+    /// Number of credentials in the batch
+    pub count: u32,
+
+    /// Off-chain credential schema
+    #[max_len(MAX_SCHEMA_ID_LEN)]
+    pub schema_id: String,
+
+    pub anchored_at: i64,
+
+    /// Batch-wide revocation
+    pub revoked: bool,
+    pub revocation_reason: Option<RevocationReason>,
+    #[max_len(REVOCATION_NOTE_MAX)]
+    pub revocation_note: String,
+    pub revoked_at: i64,
+
+    pub bump: u8,
+}
+
+impl CredentialBatch {
+    /// Proof length for this batch: ceil(log2(count)).
+    pub fn depth(&self) -> usize {
+        (u32::BITS - self.count.saturating_sub(1).leading_zeros()) as usize
+    }
+
+    /// Checks that `leaf` sits at `index` under `root`. Proofs must have exactly
+    /// `depth()` siblings, so an inner node can't pass for a leaf.
+    pub fn includes(&self, leaf: &[u8; 32], index: u32, proof: &[[u8; 32]]) -> bool {
+        if index >= self.count || proof.len() != self.depth() {
+            return false;
+        }
+        let mut node = *leaf;
+        let mut position = index;
+        for sibling in proof {
+            node = if position & 1 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            };
+            position >>= 1;
+        }
+        node == self.root
+    }
+}
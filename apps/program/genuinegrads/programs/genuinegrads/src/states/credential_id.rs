@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+pub const CREDENTIAL_ID_SEED: &[u8] = b"credential_id";
+
+/// Maps a university's off-chain credential id to the asset minted for it.
+/// Created by the mint, so a retried or duplicated mint of the same id fails.
+#[account]
+#[derive(InitSpace)]
+pub struct CredentialId {
+    /// University that issued the credential
+    pub university: Pubkey,
+
+    /// sha256 of the backend credential id
+    pub credential_id: [u8; 32],
+
+    /// Asset id of the certificate minted for this credential
+    pub asset_id: Pubkey,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+use crate::states::uri_scheme;
 
 pub const GLOBAL_CONFIG_SEED: &[u8] = b"global-config";
 
@@ -11,4 +13,18 @@ pub struct GlobalConfig {
     pub frozen: bool,
     /// Bump for PDA
     pub bump: u8,
-}
\ No newline at end of file
+    /// Bitmask of URI_SCHEME_* accepted for certificate metadata URIs.
+    /// Appended last; accounts created before it are grown by `migrate_global_config`.
+    pub allowed_uri_schemes: u8,
+}
+
+impl GlobalConfig {
+    /// Certificate metadata URIs must use one of the allowlisted schemes.
+    pub fn validate_uri(&self, uri: &str) -> Result<()> {
+        require!(
+            uri_scheme(uri).is_some_and(|scheme| self.allowed_uri_schemes & scheme != 0),
+            GenuineGradsError::UriSchemeNotAllowed
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::GenuineGradsError;
+
+pub const MINT_CONSENT_SEED: &[u8] = b"mint_consent";
+
+/// A student's advance acceptance of one specific credential, for universities
+/// that require consent before publishing on chain.
+#[account]
+#[derive(InitSpace)]
+pub struct MintConsent {
+    /// Student wallet that will receive the certificate
+    pub student: Pubkey,
+
+    /// University allowed to mint it
+    pub university: Pubkey,
+
+    /// name_uri_hash(name, uri) of the certificate being accepted
+    pub credential_hash: [u8; 32],
+
+    /// Set once the consent has been used by a mint
+    pub asset_id: Option<Pubkey>,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl MintConsent {
+    /// Loads an optional consent passed as a remaining account. This program's id stands for
+    /// none (Anchor's placeholder for optional accounts); anything else must be the unused
+    /// consent PDA ["mint_consent", university, student, credential_hash].
+    pub fn load_unused(
+        info: &AccountInfo,
+        university: &Pubkey,
+        student: &Pubkey,
+        credential_hash: &[u8; 32],
+    ) -> Result<Option<Self>> {
+        if info.key() == crate::ID {
+            return Ok(None);
+        }
+        let (expected, _) = Pubkey::find_program_address(
+            &[MINT_CONSENT_SEED, university.as_ref(), student.as_ref(), credential_hash.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, GenuineGradsError::StudentConsentRequired);
+        require_keys_eq!(*info.owner, crate::ID, GenuineGradsError::StudentConsentRequired);
+        let consent = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(consent.asset_id.is_none(), GenuineGradsError::ConsentAlreadyUsed);
+        Ok(Some(consent))
+    }
+}
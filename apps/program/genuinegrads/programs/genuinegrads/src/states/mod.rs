@@ -8,4 +8,49 @@ pub mod university_collection;
 pub use university_collection::*;
 
 pub mod university_tree;
-pub use university_tree::*;
\ No newline at end of file
+pub use university_tree::*;
+
+pub mod program_allowlist;
+pub use program_allowlist::*;
+
+pub mod certificate_record;
+pub use certificate_record::*;
+
+pub mod revocation_list;
+pub use revocation_list::*;
+
+pub mod collection_policy;
+pub use collection_policy::*;
+
+pub mod certificate_claim;
+pub use certificate_claim::*;
+
+pub mod recovery_request;
+pub use recovery_request::*;
+
+pub mod mint_consent;
+pub use mint_consent::*;
+
+pub mod co_issuance_approval;
+pub use co_issuance_approval::*;
+
+pub mod credential_id;
+pub use credential_id::*;
+
+pub mod cohort;
+pub use cohort::*;
+
+pub mod credential_batch;
+pub use credential_batch::*;
+
+pub mod verifying_key;
+pub use verifying_key::*;
+
+pub mod achievement_commitment;
+pub use achievement_commitment::*;
+
+pub mod proof_request;
+pub use proof_request::*;
+
+pub mod nullifier;
+pub use nullifier::*;
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+/// Marks an `ach_member_v2` nullifier as used. Its existence is the replay guard:
+/// a second verification with the same nullifier fails to create the PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct Nullifier {
+    /// Poseidon(student_secret, salt, request_context), big-endian
+    pub nullifier: [u8; 32],
+
+    /// Request the proof answered
+    pub proof_request: Pubkey,
+
+    /// Commitment the proof was made against
+    pub achievement_commitment: Pubkey,
+
+    pub used_at: i64,
+    pub bump: u8,
+}
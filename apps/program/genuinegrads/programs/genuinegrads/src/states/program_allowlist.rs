@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+pub const PROGRAM_ALLOWLIST_SEED: &[u8] = b"program-allowlist";
+
+/// Delay between proposing and applying a new set of external program IDs.
+pub const PROGRAM_ALLOWLIST_TIMELOCK_SECS: i64 = 48 * 60 * 60;
+
+/// Trusted external programs the certificate instructions CPI into.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ExternalProgramIds {
+    pub bubblegum: Pubkey,
+    pub mpl_core: Pubkey,
+    pub account_compression: Pubkey,
+    pub noop: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramAllowlist {
+    /// Program-level super admin (mirrors GlobalConfig.owner)
+    pub admin: Pubkey,
+    /// Bumped every time a pending update is applied
+    pub version: u32,
+    /// Program IDs currently trusted by create_tree_v2 / mint / burn
+    pub programs: ExternalProgramIds,
+    /// Proposed replacement, applicable once `pending_eta` has passed
+    pub pending: Option<ExternalProgramIds>,
+    pub pending_eta: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
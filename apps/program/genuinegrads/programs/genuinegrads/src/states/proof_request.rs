@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+pub const PROOF_REQUEST_SEED: &[u8] = b"proof_request";
+
+/// A verifier's request for an achievement proof. `ach_member_v2` proofs take `context`
+/// as a public input, so a proof made for one request can't satisfy another.
+#[account]
+#[derive(InitSpace)]
+pub struct ProofRequest {
+    /// Verifier (e.g. employer) that opened the request
+    pub verifier: Pubkey,
+
+    /// Verifier-chosen nonce (part of the PDA)
+    pub nonce: [u8; 32],
+
+    /// sha256(proof_request address) mod r, big-endian; the circuit's request_context
+    pub context: [u8; 32],
+
+    /// Achievement the verifier asks for; None accepts any registered achievement
+    pub achievement_hash: Option<[u8; 32]>,
+
+    /// Proofs are refused after this time
+    pub expires_at: Option<i64>,
+
+    /// Proofs accepted so far (one per commitment, enforced by nullifiers)
+    pub verified_count: u32,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
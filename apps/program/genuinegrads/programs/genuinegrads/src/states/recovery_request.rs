@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+pub const RECOVERY_REQUEST_SEED: &[u8] = b"recovery";
+
+/// Program PDA that is the authority of each collection's PermanentTransferDelegate, so
+/// only `recover_certificate` (after the timelock) can move certificates without the owner.
+/// PDA = ["recovery_delegate", core_collection]
+pub const RECOVERY_DELEGATE_SEED: &[u8] = b"recovery_delegate";
+
+/// Delay between requesting and executing a recovery; the current owner can cancel meanwhile.
+#[cfg(not(feature = "short-recovery-timelock"))]
+pub const RECOVERY_TIMELOCK_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Localnet test builds only (enabled by the default `localnet` feature),
+/// so the test suite can execute a recovery.
+#[cfg(feature = "short-recovery-timelock")]
+pub const RECOVERY_TIMELOCK_SECS: i64 = 10;
+
+/// Pending issuer-assisted move of a certificate to a new wallet.
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    /// The University account that issued the certificate
+    pub university: Pubkey,
+
+    /// University authority that opened the request (receives rent on close)
+    pub authority: Pubkey,
+
+    /// Bubblegum asset id being recovered
+    pub asset_id: Pubkey,
+
+    /// Current (lost) owner; can cancel the request by signing
+    pub old_owner: Pubkey,
+
+    /// Wallet the certificate moves to
+    pub new_owner: Pubkey,
+
+    pub requested_at: i64,
+    /// Earliest execution time (requested_at + RECOVERY_TIMELOCK_SECS)
+    pub eta: i64,
+    pub bump: u8,
+}
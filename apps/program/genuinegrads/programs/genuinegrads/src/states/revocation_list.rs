@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+pub const REVOCATION_LIST_SEED: &[u8] = b"revocation_list";
+
+/// Leaves covered by one shard (bits); a depth-14 tree needs a single shard.
+pub const REVOCATION_SHARD_BITS: u64 = 1 << 14;
+pub const REVOCATION_SHARD_BYTES: usize = (REVOCATION_SHARD_BITS / 8) as usize;
+
+/// Revocation bitstring for one shard of a UniversityTree, in the spirit of StatusList2021.
+/// Bit `i` (LSB-first within each byte) is leaf index `shard_index * REVOCATION_SHARD_BITS + i`;
+/// a set bit means the certificate is revoked or burned.
+/// PDA = ["revocation_list", university_tree, shard_index (LE u32)]
+#[account(zero_copy)]
+pub struct RevocationList {
+    /// The UniversityTree this shard belongs to
+    pub university_tree: Pubkey,
+    /// SPL-Compression Merkle tree (mirrors university_tree.merkle_tree)
+    pub merkle_tree: Pubkey,
+    pub shard_index: u32,
+    /// Number of bits currently set
+    pub revoked_count: u32,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub bits: [u8; REVOCATION_SHARD_BYTES],
+}
+
+impl RevocationList {
+    pub const LEN: usize = 32 + 32 + 4 + 4 + 8 + 1 + 7 + REVOCATION_SHARD_BYTES;
+
+    /// Shard holding `leaf_index`
+    pub fn shard_for(leaf_index: u64) -> u32 {
+        (leaf_index / REVOCATION_SHARD_BITS) as u32
+    }
+
+    /// Number of shards needed to cover a tree of `max_depth`
+    pub fn shard_count(max_depth: u32) -> u32 {
+        let leaves = 1u64 << max_depth;
+        leaves.div_ceil(REVOCATION_SHARD_BITS) as u32
+    }
+
+    fn position(leaf_index: u64) -> (usize, u8) {
+        let bit = leaf_index % REVOCATION_SHARD_BITS;
+        ((bit / 8) as usize, 1u8 << (bit % 8))
+    }
+
+    pub fn is_revoked(&self, leaf_index: u64) -> bool {
+        let (byte, mask) = Self::position(leaf_index);
+        self.bits[byte] & mask != 0
+    }
+
+    /// Sets or clears the bit for `leaf_index`; returns whether it changed.
+    pub fn set_revoked(&mut self, leaf_index: u64, revoked: bool) -> bool {
+        if self.is_revoked(leaf_index) == revoked {
+            return false;
+        }
+        let (byte, mask) = Self::position(leaf_index);
+        if revoked {
+            self.bits[byte] |= mask;
+            self.revoked_count += 1;
+        } else {
+            self.bits[byte] &= !mask;
+            self.revoked_count -= 1;
+        }
+        true
+    }
+}
@@ -19,4 +19,8 @@ pub struct University {
     pub is_active: bool,
     pub created_at: i64,
     pub bump: u8,
+    /// Policy: certificates may only be minted with the student's consent
+    /// (recipient co-signs the mint, or a MintConsent PDA exists).
+    /// Appended last; accounts created before it are grown by `migrate_university`.
+    pub require_student_consent: bool,
 }
\ No newline at end of file
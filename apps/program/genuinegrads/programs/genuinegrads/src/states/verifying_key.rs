@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::groth16::{Groth16VerifyingKey, G1_LEN, VK_HEADER_LEN};
+
+pub const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
+
+/// Circuit ids name a circuit family across versions, e.g. "ach_member".
+pub const MAX_CIRCUIT_ID_LEN: usize = 32;
+
+/// Upper bound on public inputs per circuit; sizes the key data.
+pub const MAX_PUBLIC_INPUTS: u8 = 16;
+
+/// Key data length for MAX_PUBLIC_INPUTS public inputs.
+pub const MAX_VERIFYING_KEY_DATA_LEN: usize = VK_HEADER_LEN + G1_LEN * (MAX_PUBLIC_INPUTS as usize + 1);
+
+/// Published Groth16 verifying key of one circuit version. Uploaded in chunks by the
+/// super admin, then finalized; a finalized key is never edited, only deprecated,
+/// so proofs made against an older version stay checkable after an upgrade.
+#[account]
+#[derive(InitSpace)]
+pub struct VerifyingKey {
+    /// Super admin that published the key
+    pub admin: Pubkey,
+
+    #[max_len(MAX_CIRCUIT_ID_LEN)]
+    pub circuit_id: String,
+    pub version: u16,
+    pub public_inputs: u8,
+
+    /// sha256 of the complete key data, fixed at creation
+    pub key_hash: [u8; 32],
+
+    /// alpha_g1 || beta_g2 || gamma_g2 || delta_g2 || ic[0..=public_inputs],
+    /// in the alt_bn128 syscall encoding
+    #[max_len(MAX_VERIFYING_KEY_DATA_LEN)]
+    pub data: Vec<u8>,
+
+    pub finalized: bool,
+    pub deprecated: bool,
+    pub created_at: i64,
+    pub finalized_at: i64,
+    pub deprecated_at: i64,
+
+    pub bump: u8,
+}
+
+impl VerifyingKey {
+    /// Key data length for a circuit with `public_inputs` public inputs.
+    pub fn data_len(public_inputs: u8) -> usize {
+        VK_HEADER_LEN + G1_LEN * (public_inputs as usize + 1)
+    }
+
+    /// The key in the form `verify_groth16` takes; `None` until the data is complete.
+    pub fn groth16(&self) -> Option<Groth16VerifyingKey<'_>> {
+        if self.data.len() != Self::data_len(self.public_inputs) {
+            return None;
+        }
+        Groth16VerifyingKey::from_bytes(&self.data)
+    }
+}
@@ -1,10 +1,244 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
+use crate::errors::GenuineGradsError;
+use crate::states::CoIssuer;
+use mpl_bubblegum::types::{
+    Collection, Creator, MetadataArgs, MetadataArgsV2, TokenProgramVersion,
+};
+
+#[cfg(not(any(feature = "localnet", feature = "devnet", feature = "mainnet")))]
+compile_error!("Select a cluster feature: `localnet` (default), `devnet` or `mainnet`.");
+
+#[cfg(any(
+    all(feature = "localnet", feature = "devnet"),
+    all(feature = "localnet", feature = "mainnet"),
+    all(feature = "devnet", feature = "mainnet"),
+))]
+compile_error!("Select one cluster feature; use `--no-default-features` with `devnet` or `mainnet`.");
+
+/// Default external program IDs for the cluster selected at build time. They seed
+/// `ProgramAllowlist`, which is what instructions check against at runtime.
+#[cfg(feature = "localnet")]
+pub mod cluster {
+    use anchor_lang::prelude::*;
+
+    // Fixture deployments loaded by the local validator. Anchor.toml clones the
+    // devnet programs at these addresses; change them if you load your own .so files.
+    pub const MPL_BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+    pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+    pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW");
+    pub const NOOP_ID: Pubkey = pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3");
+}
+
+#[cfg(all(feature = "devnet", not(feature = "localnet")))]
+pub mod cluster {
+    use anchor_lang::prelude::*;
+
+    pub const MPL_BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+    pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+    pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW");
+    pub const NOOP_ID: Pubkey = pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3");
+}
+
+#[cfg(all(feature = "mainnet", not(any(feature = "localnet", feature = "devnet"))))]
+pub mod cluster {
+    use anchor_lang::prelude::*;
+
+    pub const MPL_BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+    pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+    pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW");
+    pub const NOOP_ID: Pubkey = pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3");
+}
+
+pub use cluster::*;
+
+/// Seed Bubblegum uses for the PDA that signs its CPIs into MPL Core.
+pub const MPL_CORE_CPI_SIGNER_SEED: &[u8] = b"mpl_core_cpi_signer";
+
+/// Bubblegum's MPL Core CPI signer: PDA = ["mpl_core_cpi_signer"] under Bubblegum.
+pub fn mpl_core_cpi_signer(bubblegum_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MPL_CORE_CPI_SIGNER_SEED], bubblegum_program).0
+}
+
+/// Seed Bubblegum uses for compressed asset ids.
+pub const ASSET_SEED: &[u8] = b"asset";
+
+/// Bubblegum asset id of a leaf: PDA = ["asset", merkle_tree, nonce (LE)] under Bubblegum.
+pub fn asset_id(bubblegum_program: &Pubkey, merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ASSET_SEED, merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        bubblegum_program,
+    )
+    .0
+}
+
+/// Number of leaves minted so far (== nonce of the next leaf), read from Bubblegum's tree_config.
+pub fn tree_num_minted(tree_config: &AccountInfo) -> Result<u64> {
+    let data = tree_config.try_borrow_data()?;
+    let config = mpl_bubblegum::accounts::TreeConfig::from_bytes(&data)
+        .map_err(|_| GenuineGradsError::InvalidTreeConfig)?;
+    Ok(config.num_minted)
+}
+
+/// Grows a program-owned account to `new_len` for a layout migration, topping up rent from
+/// `payer`. The new trailing bytes are zeroed, i.e. appended fields start at their default.
+#[allow(deprecated)]
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let missing = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.clone(),
+                system_program::Transfer { from: payer.clone(), to: account.clone() },
+            ),
+            missing,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Creates a program-owned PDA with Anchor `init` semantics: lamports already sent to the
+/// address are kept (top up, then allocate and assign), so pre-funding a predictable PDA
+/// cannot block its creation.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: account.clone() },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let missing = rent.saturating_sub(current);
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.clone(),
+                system_program::Transfer { from: payer.clone(), to: account.clone() },
+            ),
+            missing,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system.clone(),
+            system_program::Allocate { account_to_allocate: account.clone() },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system.clone(),
+            system_program::Assign { account_to_assign: account.clone() },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// Account-compression tree layout: 56-byte header, then
+// sequence_number (u64), active_index (u64), buffer_size (u64), change_logs[max_buffer_size]
+// where each change log is root (32) + path (32 * max_depth) + index (u32) + padding (u32).
+const MERKLE_TREE_HEADER_LEN: usize = 56;
+const MERKLE_TREE_CHANGE_LOGS_OFFSET: usize = MERKLE_TREE_HEADER_LEN + 24;
+
+/// Current root of an account-compression Merkle tree (root of the active change log).
+pub fn current_merkle_root(merkle_tree: &AccountInfo, max_depth: u32, max_buffer_size: u32) -> Result<[u8; 32]> {
+    let data = merkle_tree.try_borrow_data()?;
+    let read_u64 = |offset: usize| -> Result<u64> {
+        let bytes = data
+            .get(offset..offset + 8)
+            .ok_or(GenuineGradsError::InvalidMerkleTree)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    let active_index = read_u64(MERKLE_TREE_HEADER_LEN + 8)? as usize;
+    require!(active_index < max_buffer_size as usize, GenuineGradsError::InvalidMerkleTree);
+
+    let change_log_len = 32 + 32 * max_depth as usize + 8;
+    let root_offset = MERKLE_TREE_CHANGE_LOGS_OFFSET + active_index * change_log_len;
+    let root = data
+        .get(root_offset..root_offset + 32)
+        .ok_or(GenuineGradsError::InvalidMerkleTree)?;
+    Ok(root.try_into().unwrap())
+}
+
+/// Digest of a leaf's name and uri (length-prefixed) used by the metadata hash chain.
+pub fn name_uri_hash(name: &str, uri: &str) -> [u8; 32] {
+    hashv(&[
+        &(name.len() as u32).to_le_bytes(),
+        name.as_bytes(),
+        &(uri.len() as u32).to_le_bytes(),
+        uri.as_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Creators of a certificate leaf: the minting university authority (always verified)
+/// followed by any joint-degree co-issuers. Shares are split evenly, remainder to the minter.
+pub fn certificate_creators(authority: Pubkey, co_issuers: &[CoIssuer]) -> Vec<Creator> {
+    let share = 100 / (co_issuers.len() as u8 + 1);
+    let mut creators = vec![Creator {
+        address: authority,
+        verified: true,
+        share: 100 - share * co_issuers.len() as u8,
+    }];
+    creators.extend(co_issuers.iter().map(|co| Creator {
+        address: co.authority,
+        verified: co.verified,
+        share,
+    }));
+    creators
+}
+
+/// Bubblegum data hash of a V2 leaf. Bubblegum hashes V2 metadata as its V1
+/// `MetadataArgs` equivalent: no edition nonce or uses, collection verified.
+pub fn leaf_data_hash(metadata: &MetadataArgsV2) -> Result<[u8; 32]> {
+    let v1 = MetadataArgs {
+        name: metadata.name.clone(),
+        symbol: metadata.symbol.clone(),
+        uri: metadata.uri.clone(),
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        primary_sale_happened: metadata.primary_sale_happened,
+        is_mutable: metadata.is_mutable,
+        edition_nonce: None,
+        token_standard: metadata.token_standard.clone(),
+        collection: metadata.collection.map(|key| Collection { verified: true, key }),
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: metadata.creators.clone(),
+    };
+    mpl_bubblegum::hash::hash_metadata(&v1).map_err(|_| error!(GenuineGradsError::MetadataMismatch))
+}
 
 #[derive(Clone)]
 pub struct MplBubblegum;
 impl Id for MplBubblegum {
     fn id() -> Pubkey {
-        mpl_bubblegum::ID
+        MPL_BUBBLEGUM_ID
     }
 }
 
@@ -12,7 +246,7 @@ impl Id for MplBubblegum {
 pub struct MplCore;
 impl Id for MplCore {
     fn id() -> Pubkey {
-        mpl_core::ID
+        MPL_CORE_ID
     }
 }
 
@@ -20,7 +254,7 @@ impl Id for MplCore {
 pub struct Noop;
 impl Id for Noop {
     fn id() -> Pubkey {
-        pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3")
+        NOOP_ID
     }
 }
 
@@ -28,14 +262,7 @@ impl Id for Noop {
 pub struct SplAccountCompression;
 impl Id for SplAccountCompression {
     fn id() -> Pubkey {
-        pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW")
+        SPL_ACCOUNT_COMPRESSION_ID
     }
 }
 
-// cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK compr
-
-// CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d core
-
-// BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY buble
-
-// noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV noop
\ No newline at end of file
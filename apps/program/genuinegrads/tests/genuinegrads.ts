@@ -10,22 +10,52 @@ import {
   PublicKey,
   SystemProgram,
   AccountMeta,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import { readFileSync } from "fs";
+import path from "path";
 import { Genuinegrads } from "../target/types/genuinegrads";
 import {
   getMerkleTreeSize,
 } from "@metaplex-foundation/spl-account-compression";
+import { ConcurrentMerkleTreeAccount, emptyNode, hash } from "@solana/spl-account-compression";
 
 // Seeds
 const GLOBAL_SEED = Buffer.from("global-config");
 const UNIVERSITY_SEED = Buffer.from("university");
 const UNI_COLLECTION_SEED = Buffer.from("university_collection");
 const UNI_TREE_SEED = Buffer.from("university_tree");
+const PROGRAM_ALLOWLIST_SEED = Buffer.from("program-allowlist");
+const CERTIFICATE_SEED = Buffer.from("certificate");
+const REVOCATION_LIST_SEED = Buffer.from("revocation_list");
+const COLLECTION_POLICY_SEED = Buffer.from("collection_policy");
+const CERTIFICATE_CLAIM_SEED = Buffer.from("certificate_claim");
+const RECOVERY_SEED = Buffer.from("recovery");
+const RECOVERY_DELEGATE_SEED = Buffer.from("recovery_delegate");
+const MINT_CONSENT_SEED = Buffer.from("mint_consent");
+const CO_ISSUANCE_SEED = Buffer.from("co_issuance");
+const CREDENTIAL_ID_SEED = Buffer.from("credential_id");
+const COHORT_SEED = Buffer.from("cohort");
+const CREDENTIAL_BATCH_SEED = Buffer.from("credential_batch");
+const VERIFYING_KEY_SEED = Buffer.from("verifying_key");
+const ACHIEVEMENT_COMMITMENT_SEED = Buffer.from("achievement_commitment");
+const PROOF_REQUEST_SEED = Buffer.from("proof_request");
+const NULLIFIER_SEED = Buffer.from("nullifier");
 
 const SPL_NOOP_PROGRAM_ID = new PublicKey("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3")
 const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW")
-const mplCoreCpiSigner = new PublicKey("CbNY3JiXdXNE9tPNEk1aRZVEkWdj2v7kfJLNQwZZgpXk");
+// Bubblegum TransferV2 instruction discriminator
+const BUBBLEGUM_TRANSFER_V2_DISCRIMINATOR = [119, 40, 6, 235, 234, 221, 248, 49];
+// Bubblegum's MPL Core CPI signer: PDA = ["mpl_core_cpi_signer"] under Bubblegum
+const [mplCoreCpiSigner] = PublicKey.findProgramAddressSync(
+  [Buffer.from("mpl_core_cpi_signer")],
+  new PublicKey(MPL_BUBBLEGUM_PROGRAM_ID)
+);
 
 describe("genuinegrads", () => {
   const provider = anchor.AnchorProvider.env();
@@ -39,6 +69,7 @@ describe("genuinegrads", () => {
 
   // Global config PDA
   let globalPda: PublicKey;
+  let allowlistPda: PublicKey;
 
   // University PDA(s)
   let uniPda: PublicKey;
@@ -52,12 +83,17 @@ describe("genuinegrads", () => {
   let merkleTree: Keypair;
   let treeConfigPda: PublicKey;
   let uniTreePda: PublicKey;
+  let revocationListPda: PublicKey;
 
   // Recipient (student)
   let student: Keypair;
 
   // Minted certificate asset ID (set during mint_certificate_v2 test)
   let mintedAssetId: PublicKey;
+  // SHA-256 of the first certificate's PDF diploma
+  const diplomaDigest = Array.from(createHash("sha256").update("alice-diploma.pdf").digest());
+  // Wallet that claims the escrowed certificate (leaf 3)
+  const graduate = Keypair.generate();
 
   async function airdrop(pubkey: PublicKey, sol = 5) {
     const sig = await provider.connection.requestAirdrop(
@@ -74,6 +110,13 @@ describe("genuinegrads", () => {
     );
   }
 
+  function findProgramAllowlist(global: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [PROGRAM_ALLOWLIST_SEED, global.toBuffer()],
+      program.programId
+    );
+  }
+
   function findUniversity(authority: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [UNIVERSITY_SEED, authority.toBuffer()],
@@ -95,6 +138,81 @@ describe("genuinegrads", () => {
     );
   }
 
+  function findCollectionPolicy(collection: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [COLLECTION_POLICY_SEED, collection.toBuffer()],
+      program.programId
+    );
+  }
+
+  function findCertificateClaim(assetId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [CERTIFICATE_CLAIM_SEED, assetId.toBuffer()],
+      program.programId
+    );
+  }
+
+  function findMintConsent(uni: PublicKey, student: PublicKey, credentialHash: number[]): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [MINT_CONSENT_SEED, uni.toBuffer(), student.toBuffer(), Buffer.from(credentialHash)],
+      program.programId
+    );
+  }
+
+  function findCredentialId(uni: PublicKey, credentialId: number[]): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [CREDENTIAL_ID_SEED, uni.toBuffer(), Buffer.from(credentialId)],
+      program.programId
+    );
+  }
+
+  function findCohort(uni: PublicKey, programme: string, year: number): [PublicKey, number] {
+    const y = Buffer.alloc(2);
+    y.writeUInt16LE(year);
+    return PublicKey.findProgramAddressSync(
+      [COHORT_SEED, uni.toBuffer(), Buffer.from(programme), y],
+      program.programId
+    );
+  }
+
+  // sha256 of a backend credential id (the on-chain idempotency key)
+  function credentialIdFor(id: string): number[] {
+    return Array.from(createHash("sha256").update(id).digest());
+  }
+
+  // SHA-256 of a stand-in metadata JSON document for `uri`
+  function metadataHashFor(uri: string): number[] {
+    const json = JSON.stringify({ name: "GenuineGrads certificate", uri });
+    return Array.from(createHash("sha256").update(json).digest());
+  }
+
+  // Mirrors utils::name_uri_hash (length-prefixed name and uri)
+  function nameUriHash(name: string, uri: string): number[] {
+    const n = Buffer.from(name);
+    const u = Buffer.from(uri);
+    const nLen = Buffer.alloc(4);
+    nLen.writeUInt32LE(n.length);
+    const uLen = Buffer.alloc(4);
+    uLen.writeUInt32LE(u.length);
+    return Array.from(createHash("sha256").update(Buffer.concat([nLen, n, uLen, u])).digest());
+  }
+
+  function findCertificateRecord(assetId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [CERTIFICATE_SEED, assetId.toBuffer()],
+      program.programId
+    );
+  }
+
+  function findRevocationList(uniTree: PublicKey, shardIndex: number): [PublicKey, number] {
+    const shard = Buffer.alloc(4);
+    shard.writeUInt32LE(shardIndex);
+    return PublicKey.findProgramAddressSync(
+      [REVOCATION_LIST_SEED, uniTree.toBuffer(), shard],
+      program.programId
+    );
+  }
+
   function findTreeConfig(merkleTree: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [merkleTree.toBuffer()],
@@ -115,6 +233,195 @@ describe("genuinegrads", () => {
     return assetId;
   }
 
+  // Helper to fetch asset proof from DAS API
+  async function getAssetWithProof(assetId: PublicKey): Promise<{
+    root: number[];
+    dataHash: number[];
+    creatorHash: number[];
+    nonce: number;
+    index: number;
+    proof: PublicKey[];
+  }> {
+    const rpcEndpoint = provider.connection.rpcEndpoint;
+
+    const response = await fetch(rpcEndpoint, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({
+        jsonrpc: "2.0",
+        id: "get-asset-proof",
+        method: "getAssetProof",
+        params: { id: assetId.toBase58() },
+      }),
+    });
+
+    const json = await response.json() as { result?: any };
+    const result = json.result;
+
+    if (!result) {
+      throw new Error(`Asset proof not found for ${assetId.toBase58()}`);
+    }
+
+    return {
+      root: Array.from(Buffer.from(result.root, "base64")),
+      dataHash: Array.from(Buffer.from(result.data_hash || result.dataHash, "base64")),
+      creatorHash: Array.from(Buffer.from(result.creator_hash || result.creatorHash, "base64")),
+      nonce: result.nonce,
+      index: result.index ?? result.leaf_index ?? 0,
+      proof: (result.proof as string[]).map((p: string) => new PublicKey(p)),
+    };
+  }
+
+  // Leaf args and proof accounts for instructions that act on an existing leaf
+  async function leafProof(assetId: PublicKey, flags: number | null = null) {
+    // Give the indexer time to catch up with the latest change to the leaf
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const proofData = await getAssetWithProof(assetId);
+    return {
+      leaf: {
+        root: proofData.root,
+        dataHash: proofData.dataHash,
+        creatorHash: proofData.creatorHash,
+        nonce: new anchor.BN(proofData.nonce),
+        index: proofData.index,
+        assetDataHash: null,
+        flags,
+      },
+      proofAccounts: proofData.proof.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: false,
+      })) as AccountMeta[],
+    };
+  }
+
+  // Nonce of the next leaf (Bubblegum TreeConfig.num_minted, after two pubkeys and a u64)
+  async function nextLeafIndex(): Promise<number> {
+    const info = await provider.connection.getAccountInfo(treeConfigPda);
+    return new anchor.BN(info!.data.subarray(80, 88), "le").toNumber();
+  }
+
+  // Proof of the leaf the next mint appends, derived from the tree's rightmost path the
+  // same way the concurrent merkle tree appends. Soulbound mints lock that leaf right away.
+  async function nextLeafProof(): Promise<AccountMeta[]> {
+    const account = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      provider.connection,
+      merkleTree.publicKey
+    );
+    const depth = account.getMaxDepth();
+    const { proof, leaf, index } = account.tree.rightMostPath;
+
+    const nodes: Buffer[] = [];
+    // Lowest level where the next leaf's path meets the last leaf's path
+    const intersection = index === 0 ? depth : Math.log2(index & -index);
+    let node = leaf.toBuffer();
+    for (let i = 0; i < depth; i++) {
+      if (i < intersection) {
+        const sibling = proof[i].toBuffer();
+        node = ((index - 1) >> i) & 1 ? hash(sibling, node) : hash(node, sibling);
+        nodes.push(emptyNode(i));
+      } else if (i === intersection) {
+        nodes.push(node);
+      } else {
+        nodes.push(proof[i].toBuffer());
+      }
+    }
+    return nodes.map((n) => ({ pubkey: new PublicKey(n), isSigner: false, isWritable: false }));
+  }
+
+  // mint_certificate_v2 args. Fixtures default to transferable so their leaf flags stay 0;
+  // soulbound mints pass `transferable: false` and the new leaf's proof.
+  function mintArgs(name: string, uri: string, assetId: PublicKey, overrides: Record<string, any> = {}) {
+    return {
+      name,
+      uri,
+      metadataHash: metadataHashFor(uri),
+      documents: [],
+      recipient: student.publicKey,
+      attachCollection: true,
+      assetId,
+      transferable: true,
+      validFrom: null,
+      validUntil: null,
+      claim: null,
+      coIssuers: [],
+      credentialId: null,
+      ...overrides,
+    };
+  }
+
+  // Accounts of a mint_certificate_v2 call into the main tree and collection
+  function mintAccounts(overrides: Record<string, PublicKey | null> = {}) {
+    return {
+      universityAuthority: uniAuth.publicKey,
+      globalConfig: globalPda,
+      university: uniPda,
+      universityCollection: uniCollectionPda,
+      universityTree: uniTreePda,
+      merkleTree: merkleTree.publicKey,
+      treeConfig: treeConfigPda,
+      recipient: student.publicKey,
+      coreCollection: coreCollection.publicKey,
+      collectionPolicy: findCollectionPolicy(coreCollection.publicKey)[0],
+      certificateClaim: null,
+      mintConsent: null,
+      credentialIdRecord: null,
+      cohort: null,
+      mplCoreCpiSigner,
+      programAllowlist: allowlistPda,
+      mplCoreProgram: MPL_CORE_PROGRAM_ID,
+      bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      ...overrides,
+    };
+  }
+
+  // Accounts of a mint_certificates_batch call into the main tree and collection
+  function batchMintAccounts() {
+    return {
+      universityAuthority: uniAuth.publicKey,
+      globalConfig: globalPda,
+      university: uniPda,
+      universityCollection: uniCollectionPda,
+      universityTree: uniTreePda,
+      merkleTree: merkleTree.publicKey,
+      treeConfig: treeConfigPda,
+      coreCollection: coreCollection.publicKey,
+      collectionPolicy: findCollectionPolicy(coreCollection.publicKey)[0],
+      cohort: null,
+      mplCoreCpiSigner: mplCoreCpiSigner,
+      programAllowlist: allowlistPda,
+      mplCoreProgram: MPL_CORE_PROGRAM_ID,
+      bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  // Accounts of a burn_certificate_v2 call for a student-owned leaf of the main tree
+  function burnAccounts(assetId: PublicKey, overrides: Record<string, PublicKey | null> = {}) {
+    return {
+      universityAuthority: uniAuth.publicKey,
+      certificateRecord: findCertificateRecord(assetId)[0],
+      merkleTree: merkleTree.publicKey,
+      treeConfig: treeConfigPda,
+      coreCollection: coreCollection.publicKey,
+      mplCoreCpiSigner,
+      leafOwner: student.publicKey,
+      revocationList: revocationListPda,
+      cohort: null,
+      programAllowlist: allowlistPda,
+      mplCoreProgram: MPL_CORE_PROGRAM_ID,
+      bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      ...overrides,
+    };
+  }
+
   before("bootstrap keypairs + airdrops", async () => {
     superAdmin = Keypair.generate();
     uniAuth = Keypair.generate();
@@ -130,6 +437,7 @@ describe("genuinegrads", () => {
 
     // PDAs
     [globalPda] = findGlobal(superAdmin.publicKey);
+    [allowlistPda] = findProgramAllowlist(globalPda);
     [uniPda] = findUniversity(uniAuth.publicKey);
     [uniPda2] = findUniversity(uniAuth2.publicKey);
   });
@@ -174,6 +482,64 @@ describe("genuinegrads", () => {
     });
   });
 
+  // -------------------------------------------------------
+  // program allowlist (timelocked external program IDs)
+  // -------------------------------------------------------
+  describe("program_allowlist", () => {
+    it("initializes the allowlist with the localnet feature's program IDs", async () => {
+      await program.methods
+        .initializeProgramAllowlist()
+        .accountsPartial({
+          superAdmin: superAdmin.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([superAdmin])
+        .rpc();
+
+      const list = await program.account.programAllowlist.fetch(allowlistPda);
+      expect(list.version).to.eq(1);
+      // `anchor test` builds with the default `localnet` feature (src/utils.rs)
+      expect(list.programs.bubblegum.toBase58()).to.eq(new PublicKey(MPL_BUBBLEGUM_PROGRAM_ID).toBase58());
+      expect(list.programs.mplCore.toBase58()).to.eq(new PublicKey(MPL_CORE_PROGRAM_ID).toBase58());
+      expect(list.programs.accountCompression.toBase58()).to.eq(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID.toBase58());
+      expect(list.programs.noop.toBase58()).to.eq(SPL_NOOP_PROGRAM_ID.toBase58());
+      expect(list.pending).to.eq(null);
+    });
+
+    it("refuses to apply a proposal before the timelock elapses", async () => {
+      const list = await program.account.programAllowlist.fetch(allowlistPda);
+
+      await program.methods
+        .proposeProgramAllowlistUpdate({ programs: list.programs })
+        .accountsPartial({
+          superAdmin: superAdmin.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+        })
+        .signers([superAdmin])
+        .rpc();
+
+      let threw = false;
+      try {
+        await program.methods
+          .applyProgramAllowlistUpdate()
+          .accountsPartial({
+            superAdmin: superAdmin.publicKey,
+            globalConfig: globalPda,
+            programAllowlist: allowlistPda,
+          })
+          .signers([superAdmin])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/TimelockNotElapsed|Timelock has not elapsed/i);
+      }
+      expect(threw).to.eq(true);
+    });
+  });
+
   // -------------------------------------------------------
   // register_university (inactive by default)
   // approve_university / deactivate_university (super-admin gated)
@@ -280,11 +646,12 @@ describe("genuinegrads", () => {
       coreCollection = Keypair.generate();
       [uniCollectionPda] = findUniCollection(uniPda);
 
-     const tx = await program.methods
+      await program.methods
         .createCoreCollectionV2Cpi({ name: "GG Degrees", uri: "https://coll/gg.json" })
         .accountsPartial({
           universityAuthority: uniAuth.publicKey,
           globalConfig: globalPda,
+          programAllowlist: allowlistPda,
           university: uniPda,
           universityCollection: uniCollectionPda,
           coreCollection: coreCollection.publicKey,
@@ -293,8 +660,6 @@ describe("genuinegrads", () => {
         })
         .signers([uniAuth, coreCollection])
         .rpc();
-      
-      console.log(`tx for created core collection: ${tx}`)
 
       // Verify ownership + our record
       const ai = await provider.connection.getAccountInfo(
@@ -321,8 +686,9 @@ describe("genuinegrads", () => {
     const IS_PUBLIC = true;
 
     it("creates a Bubblegum V2 tree via CPI and records it", async () => {
-      // 1. Generate new merkle tree keypair
-      merkleTree = Keypair.generate();
+      // 1. Fixed merkle tree keypair, so asset ids are stable across runs
+      //    (the ach_member proof fixtures in tests/fixtures are bound to the asset id of leaf 9)
+      merkleTree = Keypair.fromSeed(createHash("sha256").update("genuinegrads-test-merkle-tree").digest());
       
       // 2. PRE-CREATE the merkle tree account (owned by compression program)
       const space = getMerkleTreeSize(MAX_DEPTH, MAX_BUFFER);
@@ -349,10 +715,6 @@ describe("genuinegrads", () => {
       // 4. Derive university_tree PDA from the merkle tree
       [uniTreePda] = findUniTree(merkleTree.publicKey);
 
-      console.log("Merkle Tree:", merkleTree.publicKey.toBase58());
-      console.log("Tree Config:", treeConfigPda.toBase58());
-      console.log("Uni Tree PDA:", uniTreePda.toBase58());
-
       // 5. Call createTreeV2
       await program.methods
         .createTreeV2({
@@ -367,6 +729,7 @@ describe("genuinegrads", () => {
           universityTree: uniTreePda,
           merkleTree: merkleTree.publicKey,
           treeConfig: treeConfigPda,
+          programAllowlist: allowlistPda,
           bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           logWrapper: SPL_NOOP_PROGRAM_ID,
@@ -382,387 +745,2240 @@ describe("genuinegrads", () => {
       expect(tree.maxBufferSize).to.eq(MAX_BUFFER);
       expect(tree.isPublic).to.eq(IS_PUBLIC);
     });
-  });
 
-  // -------------------------------------------------------
-  // mint_certificate_v2 (recipient must be a SystemAccount)
-  // -------------------------------------------------------
-  describe("mint_certificate_v2", () => {
-    it("mints a compressed certificate (collection attached)", async () => {
-      // MPL Core CPI signer PDA: seeds = ["cpi_signer", coreCollection, BUBBLEGUM_ID] under MPL_CORE_ID
+    it("creates the revocation list shard for the tree", async () => {
+      [revocationListPda] = findRevocationList(uniTreePda, 0);
 
-      const tx = await program.methods
-        .mintCertificateV2({
-          name: "BSc Computer Science — 2025",
-          uri: "https://certs/alice.json",
-          recipient: student.publicKey,
-          attachCollection: true,
-        })
+      await program.methods
+        .initRevocationList({ shardIndex: 0 })
         .accountsPartial({
           universityAuthority: uniAuth.publicKey,
           globalConfig: globalPda,
           university: uniPda,
-          universityCollection: uniCollectionPda,
           universityTree: uniTreePda,
-          merkleTree: merkleTree.publicKey,
-          treeConfig: treeConfigPda,
-          recipient: student.publicKey, // SystemAccount
-          coreCollection: coreCollection.publicKey,
-          mplCoreCpiSigner: mplCoreCpiSigner,
-          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
-          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
-          logWrapper: SPL_NOOP_PROGRAM_ID,
+          revocationList: revocationListPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([uniAuth])
         .rpc();
 
-        console.log(`tx: ${tx}`)
+      const list = await program.account.revocationList.fetch(revocationListPda);
+      expect(list.shardIndex).to.eq(0);
+      expect(list.revokedCount).to.eq(0);
+    });
 
-      // Derive and store the asset ID for the minted certificate (leaf index 0)
-      mintedAssetId = getAssetId(merkleTree.publicKey, 0);
-      console.log(`Minted certificate asset ID: ${mintedAssetId.toBase58()}`);
+    it("rejects a shard beyond the tree's capacity", async () => {
+      // depth 14 → 16384 leaves → a single shard
+      const [shard1] = findRevocationList(uniTreePda, 1);
+      let threw = false;
+      try {
+        await program.methods
+          .initRevocationList({ shardIndex: 1 })
+          .accountsPartial({
+            universityAuthority: uniAuth.publicKey,
+            globalConfig: globalPda,
+            university: uniPda,
+            universityTree: uniTreePda,
+            revocationList: shard1,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/RevocationShardMismatch|does not cover/i);
+      }
+      expect(threw).to.eq(true);
     });
   });
 
   // -------------------------------------------------------
-  // burn_certificate_v2 (requires merkle proof from DAS)
+  // mint_certificate_v2 (recipient must be a SystemAccount)
   // -------------------------------------------------------
-  describe("burn_certificate_v2", () => {
-    // Helper to fetch asset proof from DAS API
-    async function getAssetWithProof(assetId: PublicKey): Promise<{
-      root: number[];
-      dataHash: number[];
-      creatorHash: number[];
-      nonce: number;
-      index: number;
-      proof: PublicKey[];
-    }> {
-      const rpcEndpoint = provider.connection.rpcEndpoint;
-
-      const response = await fetch(rpcEndpoint, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({
-          jsonrpc: "2.0",
-          id: "get-asset-proof",
-          method: "getAssetProof",
-          params: { id: assetId.toBase58() },
-        }),
-      });
-
-      const json = await response.json() as { result?: any };
-      const result = json.result;
-
-      if (!result) {
-        throw new Error(`Asset proof not found for ${assetId.toBase58()}`);
-      }
-
-      return {
-        root: Array.from(Buffer.from(result.root, "base64")),
-        dataHash: Array.from(Buffer.from(result.data_hash || result.dataHash, "base64")),
-        creatorHash: Array.from(Buffer.from(result.creator_hash || result.creatorHash, "base64")),
-        nonce: result.nonce,
-        index: result.index ?? result.leaf_index ?? 0,
-        proof: (result.proof as string[]).map((p: string) => new PublicKey(p)),
-      };
-    }
+  describe("mint_certificate_v2", () => {
+    it("mints a compressed certificate (collection attached)", async () => {
+      // First leaf of the tree → nonce 0
+      mintedAssetId = getAssetId(merkleTree.publicKey, 0);
 
-    // Helper to get asset by owner from DAS
-    async function getAssetsByOwner(owner: PublicKey): Promise<any[]> {
-      const rpcEndpoint = provider.connection.rpcEndpoint;
-
-      const response = await fetch(rpcEndpoint, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({
-          jsonrpc: "2.0",
-          id: "get-assets-by-owner",
-          method: "getAssetsByOwner",
-          params: {
-            ownerAddress: owner.toBase58(),
-            page: 1,
-            limit: 10,
-          },
-        }),
-      });
+      await program.methods
+        .mintCertificateV2(
+          mintArgs("BSc Computer Science — 2025", "https://certs/alice.json", mintedAssetId, {
+            documents: [{ kind: { diploma: {} }, digest: diplomaDigest }],
+          })
+        )
+        .accountsPartial(mintAccounts())
+        .signers([uniAuth])
+        .rpc();
 
-      const json = await response.json() as { result?: { items?: any[] } };
-      return json.result?.items || [];
-    }
+      const [recordPda] = findCertificateRecord(mintedAssetId);
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.assetId.toBase58()).to.eq(mintedAssetId.toBase58());
+      expect(rec.recipient.toBase58()).to.eq(student.publicKey.toBase58());
+      expect(rec.nonce.toNumber()).to.eq(0);
+      expect(rec.isNonTransferable).to.eq(false);
+      expect(rec.metadataHash).to.deep.eq(metadataHashFor("https://certs/alice.json"));
+    });
 
-    it("burns a minted certificate with valid proof", async () => {
-      // Use the asset ID from the previously minted certificate
-      console.log(`Asset ID to burn: ${mintedAssetId.toBase58()}`);
+    it("anchors the PDF diploma digest on the record", async () => {
+      const [recordPda] = findCertificateRecord(mintedAssetId);
+      const matches = await program.methods
+        .verifyDocumentHash({ kind: { diploma: {} }, digest: diplomaDigest })
+        .accountsPartial({ certificateRecord: recordPda })
+        .view();
+      expect(matches).to.eq(true);
+
+      const tampered = Array.from(createHash("sha256").update("edited-diploma.pdf").digest());
+      const mismatch = await program.methods
+        .verifyDocumentHash({ kind: { diploma: {} }, digest: tampered })
+        .accountsPartial({ certificateRecord: recordPda })
+        .view();
+      expect(mismatch).to.eq(false);
+    });
 
-      // Wait a bit for indexer to catch up
-      await new Promise((resolve) => setTimeout(resolve, 2000));
+    it("rejects an asset id that is not the tree's next leaf", async () => {
+      let threw = false;
+      try {
+        await program.methods
+          .mintCertificateV2(mintArgs("Wrong Asset Id", "https://certs/wrong.json", getAssetId(merkleTree.publicKey, 42)))
+          .accountsPartial(mintAccounts())
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/AssetIdMismatch|Asset id does not match/i);
+      }
+      expect(threw).to.eq(true);
+    });
 
-      // Get the merkle proof for this asset
-      const proofData = await getAssetWithProof(mintedAssetId);
-      console.log(`Got proof with ${proofData.proof.length} nodes`);
+    it("rejects a validity window that ends before it starts", async () => {
+      let threw = false;
+      try {
+        await program.methods
+          .mintCertificateV2(
+            mintArgs("Bad Window", "https://certs/bad-window.json", getAssetId(merkleTree.publicKey, 1), {
+              validFrom: new anchor.BN(2_000_000_000),
+              validUntil: new anchor.BN(1_900_000_000),
+            })
+          )
+          .accountsPartial(mintAccounts())
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/InvalidValidityWindow|validity window/i);
+      }
+      expect(threw).to.eq(true);
+    });
+  });
 
-      // Convert proof nodes to remaining accounts
-      const proofAccounts: AccountMeta[] = proofData.proof.map((pubkey) => ({
-        pubkey,
-        isSigner: false,
-        isWritable: false,
-      }));
+  // -------------------------------------------------------
+  // revoke_certificate / reinstate_certificate (freeze/thaw, requires DAS proof)
+  // -------------------------------------------------------
+  describe("revoke_certificate / reinstate_certificate", () => {
+    const revokeAccounts = () => ({
+      universityAuthority: uniAuth.publicKey,
+      globalConfig: globalPda,
+      programAllowlist: allowlistPda,
+      merkleTree: merkleTree.publicKey,
+      treeConfig: treeConfigPda,
+      coreCollection: coreCollection.publicKey,
+      certificateRecord: findCertificateRecord(mintedAssetId)[0],
+      leafOwner: student.publicKey,
+      revocationList: revocationListPda,
+      cohort: null,
+      bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+    });
 
-      // Execute burn
-      const tx = await program.methods
-        .burnCertificateV2({
-          root: proofData.root as number[],
-          dataHash: proofData.dataHash as number[],
-          creatorHash: proofData.creatorHash as number[],
-          nonce: new anchor.BN(proofData.nonce),
-          index: proofData.index,
-          assetDataHash: null,
-          flags: null,
-          reason: "Certificate revoked - academic misconduct",
-        })
-        .accountsPartial({
-          universityAuthority: uniAuth.publicKey,
-          merkleTree: merkleTree.publicKey,
-          treeConfig: treeConfigPda,
-          coreCollection: coreCollection.publicKey,
-          mplCoreCpiSigner: mplCoreCpiSigner,
-          leafOwner: student.publicKey,
-          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+    it("revokes a certificate with a reason code, then reinstates it", async () => {
+      const [recordPda] = findCertificateRecord(mintedAssetId);
+      const accounts = revokeAccounts();
+      const revoke = await leafProof(mintedAssetId, 0);
+      await program.methods
+        .revokeCertificate({
+          ...revoke.leaf,
+          reason: { issuedInError: {} },
+          note: "Wrong programme on certificate",
+          caseReferenceHash: null,
         })
-        .remainingAccounts(proofAccounts)
+        .accountsPartial(accounts)
+        .remainingAccounts(revoke.proofAccounts)
         .signers([uniAuth])
         .rpc();
 
-      console.log(`Burn tx: ${tx}`);
+      let rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.status).to.deep.eq({ revoked: {} });
 
-      // Verify the asset is no longer owned by student (after indexer updates)
-      await new Promise((resolve) => setTimeout(resolve, 2000));
-      const assetsAfterBurn = await getAssetsByOwner(student.publicKey);
-      console.log(`Assets after burn: ${assetsAfterBurn.length}`);
-    });
+      const revoked = await program.methods
+        .checkRevocationStatus({ leafIndex: new anchor.BN(0) })
+        .accountsPartial({ revocationList: revocationListPda })
+        .view();
+      expect(revoked).to.eq(true);
+      expect(rec.revocationReason).to.deep.eq({ issuedInError: {} });
+      expect(rec.revocationNote).to.eq("Wrong programme on certificate");
 
-    it("fails to burn with empty reason", async () => {
-      // Mint another certificate first
+      // Leaf is now frozen at the permanent-delegate level (flags bit 1)
+      const reinstate = await leafProof(mintedAssetId, 2);
       await program.methods
-        .mintCertificateV2({
-          name: "Test Cert for Burn Validation",
-          uri: "https://certs/test.json",
-          recipient: student.publicKey,
-          attachCollection: true,
-        })
-        .accountsPartial({
-          universityAuthority: uniAuth.publicKey,
-          globalConfig: globalPda,
-          university: uniPda,
-          universityCollection: uniCollectionPda,
-          universityTree: uniTreePda,
-          merkleTree: merkleTree.publicKey,
-          treeConfig: treeConfigPda,
-          recipient: student.publicKey,
-          coreCollection: coreCollection.publicKey,
-          mplCoreCpiSigner: mplCoreCpiSigner,
-          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
-          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
-          logWrapper: SPL_NOOP_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        })
+        .reinstateCertificate(reinstate.leaf)
+        .accountsPartial(accounts)
+        .remainingAccounts(reinstate.proofAccounts)
         .signers([uniAuth])
         .rpc();
 
-      await new Promise((resolve) => setTimeout(resolve, 2000));
-
-      const assets = await getAssetsByOwner(student.publicKey);
-      if (assets.length === 0) {
-        console.log("No assets found - skipping validation test");
-        return;
-      }
+      rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.status).to.deep.eq({ active: {} });
+      expect(rec.revocationReason).to.eq(null);
+      expect(rec.revocationNote).to.eq("");
 
-      const asset = assets[0];
-      const assetId = new PublicKey(asset.id);
-      const proofData = await getAssetWithProof(assetId);
+      const list = await program.account.revocationList.fetch(revocationListPda);
+      expect(list.revokedCount).to.eq(0);
+    });
 
-      const proofAccounts: AccountMeta[] = proofData.proof.map((pubkey) => ({
-        pubkey,
-        isSigner: false,
-        isWritable: false,
-      }));
+    it("requires the super admin to reinstate a legal-order revocation", async () => {
+      const [recordPda] = findCertificateRecord(mintedAssetId);
+      const accounts = revokeAccounts();
+      const revoke = await leafProof(mintedAssetId, 0);
+      await program.methods
+        .revokeCertificate({
+          ...revoke.leaf,
+          reason: { legalOrder: {} },
+          note: null,
+          caseReferenceHash: null,
+        })
+        .accountsPartial({ ...accounts, superAdmin: superAdmin.publicKey })
+        .remainingAccounts(revoke.proofAccounts)
+        .signers([uniAuth, superAdmin])
+        .rpc();
 
+      const reinstate = await leafProof(mintedAssetId, 2);
       let threw = false;
       try {
         await program.methods
-          .burnCertificateV2({
-            root: proofData.root as number[],
-            dataHash: proofData.dataHash as number[],
-            creatorHash: proofData.creatorHash as number[],
-            nonce: new anchor.BN(proofData.nonce),
-            index: proofData.index,
-            assetDataHash: null,
-            flags: null,
-            reason: "", // Empty reason should fail
-          })
-          .accountsPartial({
-            universityAuthority: uniAuth.publicKey,
-            merkleTree: merkleTree.publicKey,
-            treeConfig: treeConfigPda,
-            coreCollection: coreCollection.publicKey,
-            mplCoreCpiSigner: mplCoreCpiSigner,
-            leafOwner: student.publicKey,
-            bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
-          })
-          .remainingAccounts(proofAccounts)
+          .reinstateCertificate(reinstate.leaf)
+          .accountsPartial(accounts)
+          .remainingAccounts(reinstate.proofAccounts)
           .signers([uniAuth])
           .rpc();
       } catch (e: any) {
         threw = true;
-        expect(e.message).to.match(/InvalidBurnReason|Invalid burn reason/i);
+        expect(e.message).to.match(/ReasonNotPermittedForCaller|not permitted/i);
       }
-      expect(threw).to.eq(true, "Expected burn with empty reason to fail");
+      expect(threw).to.eq(true, "Expected reinstate without the super admin to fail");
+
+      await program.methods
+        .reinstateCertificate(reinstate.leaf)
+        .accountsPartial({ ...accounts, superAdmin: superAdmin.publicKey })
+        .remainingAccounts(reinstate.proofAccounts)
+        .signers([uniAuth, superAdmin])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.status).to.deep.eq({ active: {} });
     });
+  });
 
-    it("fails to burn with reason exceeding 120 characters", async () => {
-      const assets = await getAssetsByOwner(student.publicKey);
-      if (assets.length === 0) {
-        console.log("No assets found - skipping validation test");
-        return;
+  // -------------------------------------------------------
+  // burn_certificate_v2 (requires merkle proof from DAS)
+  // -------------------------------------------------------
+  describe("burn_certificate_v2", () => {
+    // Leaf 1 is minted here and kept for the failure cases; reissue_certificate replaces it
+    const burnTarget = () => getAssetId(merkleTree.publicKey, 1);
+
+    async function expectBurnError(
+      args: { reason: object; note: string | null; index?: number },
+      accounts: Record<string, PublicKey | null>,
+      signer: Keypair,
+      withProof: boolean,
+      pattern: RegExp
+    ) {
+      const { leaf, proofAccounts } = await leafProof(burnTarget());
+      let threw = false;
+      try {
+        await program.methods
+          .burnCertificateV2({ ...leaf, ...args, caseReferenceHash: null })
+          .accountsPartial(burnAccounts(burnTarget(), accounts))
+          .remainingAccounts(withProof ? proofAccounts : [])
+          .signers([signer])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(pattern);
       }
+      expect(threw).to.eq(true, `Expected burn to fail with ${pattern}`);
+    }
 
-      const asset = assets[0];
-      const assetId = new PublicKey(asset.id);
-      const proofData = await getAssetWithProof(assetId);
+    it("burns a minted certificate with valid proof", async () => {
+      const { leaf, proofAccounts } = await leafProof(mintedAssetId);
+      const [recordPda] = findCertificateRecord(mintedAssetId);
+      const caseHash = Array.from(Buffer.alloc(32, 7));
+
+      await program.methods
+        .burnCertificateV2({
+          ...leaf,
+          reason: { academicMisconduct: {} },
+          note: "Plagiarism in final thesis",
+          caseReferenceHash: caseHash,
+        })
+        .accountsPartial(burnAccounts(mintedAssetId))
+        .remainingAccounts(proofAccounts)
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.status).to.deep.eq({ burned: {} });
+      expect(rec.revocationReason).to.deep.eq({ academicMisconduct: {} });
+      expect(rec.revocationNote).to.eq("Plagiarism in final thesis");
+      expect(rec.caseReferenceHash).to.deep.eq(caseHash);
+    });
+
+    it("fails to burn with empty note", async () => {
+      await program.methods
+        .mintCertificateV2(mintArgs("Test Cert for Burn Validation", "https://certs/test.json", burnTarget()))
+        .accountsPartial(mintAccounts())
+        .signers([uniAuth])
+        .rpc();
+
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: "" },
+        {},
+        uniAuth,
+        true,
+        /InvalidRevocationNote|Revocation note/i
+      );
+    });
+
+    it("fails to burn with note exceeding 120 characters", async () => {
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: "A".repeat(121) },
+        {},
+        uniAuth,
+        true,
+        /InvalidRevocationNote|Revocation note/i
+      );
+    });
+
+    it("fails to burn for a legal order without the super admin", async () => {
+      await expectBurnError(
+        { reason: { legalOrder: {} }, note: null },
+        { superAdmin: null },
+        uniAuth,
+        true,
+        /ReasonNotPermittedForCaller|not permitted/i
+      );
+    });
+
+    it("fails when non-authority tries to burn", async () => {
+      // uniAuth2 is not the university authority for this certificate
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: null },
+        { universityAuthority: uniAuth2.publicKey },
+        uniAuth2,
+        true,
+        /Unauthorized|constraint|inactive|A seeds constraint was violated/i
+      );
+    });
+
+    it("fails when the record of another leaf is passed", async () => {
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: null },
+        { certificateRecord: findCertificateRecord(mintedAssetId)[0] },
+        uniAuth,
+        true,
+        /CertificateRecordMismatch|not the record PDA/i
+      );
+    });
+
+    it("fails when the leaf index does not match the record", async () => {
+      const { leaf } = await leafProof(burnTarget());
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: null, index: leaf.index + 1 },
+        {},
+        uniAuth,
+        true,
+        /AssetIdMismatch|Asset id does not match/i
+      );
+    });
+
+    it("fails when proof is missing (no remaining accounts)", async () => {
+      await expectBurnError(
+        { reason: { issuedInError: {} }, note: null },
+        {},
+        uniAuth,
+        false,
+        /MissingMerkleProof|Missing merkle proof/i
+      );
+    });
+  });
 
+  // -------------------------------------------------------
+  // reissue_certificate (revokes leaf 1, mints its replacement)
+  // -------------------------------------------------------
+  describe("reissue_certificate", () => {
+    it("reissues a certificate and links old and new records", async () => {
+      const oldAssetId = getAssetId(merkleTree.publicKey, 1);
+      const newAssetId = getAssetId(merkleTree.publicKey, 2);
+      const [oldRecordPda] = findCertificateRecord(oldAssetId);
+      const [newRecordPda] = findCertificateRecord(newAssetId);
+
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const proofData = await getAssetWithProof(oldAssetId);
       const proofAccounts: AccountMeta[] = proofData.proof.map((pubkey) => ({
         pubkey,
         isSigner: false,
         isWritable: false,
       }));
 
-      const longReason = "A".repeat(121); // 121 characters, exceeds limit
+      await program.methods
+        .reissueCertificate({
+          root: proofData.root as number[],
+          dataHash: proofData.dataHash as number[],
+          creatorHash: proofData.creatorHash as number[],
+          nonce: new anchor.BN(proofData.nonce),
+          index: proofData.index,
+          assetDataHash: null,
+          flags: 0,
+          mode: { revoke: {} },
+          note: "Corrected graduation year",
+          name: "Test Cert (corrected)",
+          uri: "https://certs/test-corrected.json",
+          metadataHash: metadataHashFor("https://certs/test-corrected.json"),
+          documents: [],
+          newAssetId,
+          transferable: true,
+          oldProofLen: proofAccounts.length,
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          merkleTree: merkleTree.publicKey,
+          treeConfig: treeConfigPda,
+          coreCollection: coreCollection.publicKey,
+          collectionPolicy: findCollectionPolicy(coreCollection.publicKey)[0],
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          mplCoreCpiSigner: mplCoreCpiSigner,
+          oldCertificateRecord: oldRecordPda,
+          newCertificateRecord: newRecordPda,
+          revocationList: revocationListPda,
+          cohort: null,
+          mintConsent: null,
+          credentialIdRecord: null,
+          recipient: student.publicKey,
+          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+        })
+        .remainingAccounts(proofAccounts)
+        .signers([uniAuth])
+        .rpc();
+
+      const oldRec = await program.account.certificateRecord.fetch(oldRecordPda);
+      expect(oldRec.status).to.deep.eq({ revoked: {} });
+      expect(oldRec.revocationReason).to.deep.eq({ superseded: {} });
+      expect(oldRec.supersededBy.toBase58()).to.eq(newAssetId.toBase58());
+
+      const newRec = await program.account.certificateRecord.fetch(newRecordPda);
+      expect(newRec.status).to.deep.eq({ active: {} });
+      expect(newRec.recipient.toBase58()).to.eq(student.publicKey.toBase58());
+      expect(newRec.supersedes.toBase58()).to.eq(oldAssetId.toBase58());
+      expect(newRec.supersededBy).to.eq(null);
+    });
+
+    it("verifies validity of the reissued certificate; renewal needs an expiry", async () => {
+      const [recordPda] = findCertificateRecord(getAssetId(merkleTree.publicKey, 2));
+
+      // No window → valid
+      await program.methods
+        .verifyCertificateValidity()
+        .accountsPartial({ certificateRecord: recordPda })
+        .view();
 
       let threw = false;
       try {
         await program.methods
-          .burnCertificateV2({
-            root: proofData.root as number[],
-            dataHash: proofData.dataHash as number[],
-            creatorHash: proofData.creatorHash as number[],
-            nonce: new anchor.BN(proofData.nonce),
-            index: proofData.index,
-            assetDataHash: null,
-            flags: null,
-            reason: longReason,
-          })
+          .renewCertificate({ validUntil: new anchor.BN(2_000_000_000) })
           .accountsPartial({
             universityAuthority: uniAuth.publicKey,
-            merkleTree: merkleTree.publicKey,
-            treeConfig: treeConfigPda,
-            coreCollection: coreCollection.publicKey,
-            mplCoreCpiSigner: mplCoreCpiSigner,
-            leafOwner: student.publicKey,
-            bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+            globalConfig: globalPda,
+            university: uniPda,
+            certificateRecord: recordPda,
           })
-          .remainingAccounts(proofAccounts)
           .signers([uniAuth])
           .rpc();
       } catch (e: any) {
         threw = true;
-        expect(e.message).to.match(/InvalidBurnReason|Invalid burn reason/i);
+        expect(e.message).to.match(/InvalidValidityWindow|validity window/i);
       }
-      expect(threw).to.eq(true, "Expected burn with long reason to fail");
+      expect(threw).to.eq(true, "Expected renewal of a non-expiring certificate to fail");
     });
 
-    it("fails when non-authority tries to burn", async () => {
-      const assets = await getAssetsByOwner(student.publicKey);
-      if (assets.length === 0) {
-        console.log("No assets found - skipping authorization test");
-        return;
+    it("verify_certificate requires the leaf proof", async () => {
+      let threw = false;
+      try {
+        await program.methods
+          .verifyCertificate({
+            root: Array(32).fill(0),
+            owner: student.publicKey,
+            delegate: student.publicKey,
+            nonce: new anchor.BN(2),
+            index: 2,
+            dataHash: Array(32).fill(0),
+            creatorHash: Array(32).fill(0),
+            collectionHash: Array(32).fill(0),
+            assetDataHash: Array(32).fill(0),
+            flags: 0,
+          })
+          .accountsPartial({
+            globalConfig: globalPda,
+            programAllowlist: allowlistPda,
+            university: uniPda,
+            universityTree: uniTreePda,
+            merkleTree: merkleTree.publicKey,
+            revocationList: revocationListPda,
+            certificateRecord: findCertificateRecord(getAssetId(merkleTree.publicKey, 2))[0],
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          })
+          .view();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/MissingMerkleProof|Missing merkle proof/i);
       }
+      expect(threw).to.eq(true, "Expected verification without proof to fail");
+    });
 
-      const asset = assets[0];
-      const assetId = new PublicKey(asset.id);
-      const proofData = await getAssetWithProof(assetId);
-
-      const proofAccounts: AccountMeta[] = proofData.proof.map((pubkey) => ({
-        pubkey,
-        isSigner: false,
-        isWritable: false,
-      }));
+    it("verify_certificate reports a valid certificate from its leaf proof", async () => {
+      const assetId = getAssetId(merkleTree.publicKey, 2);
+      const { leaf, proofAccounts } = await leafProof(assetId);
+      const verification = await program.methods
+        .verifyCertificate({
+          root: leaf.root,
+          owner: student.publicKey,
+          // Bubblegum sets the delegate to the owner when none is given
+          delegate: student.publicKey,
+          nonce: leaf.nonce,
+          index: leaf.index,
+          dataHash: leaf.dataHash,
+          creatorHash: leaf.creatorHash,
+          // keccak(collection) and keccak("") (no asset data), as Bubblegum hashes them
+          collectionHash: Array.from(hash(coreCollection.publicKey.toBuffer(), Buffer.alloc(0))),
+          assetDataHash: Array.from(hash(Buffer.alloc(0), Buffer.alloc(0))),
+          // Transferable leaf, not frozen
+          flags: 0,
+        })
+        .accountsPartial({
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityTree: uniTreePda,
+          merkleTree: merkleTree.publicKey,
+          revocationList: revocationListPda,
+          certificateRecord: findCertificateRecord(assetId)[0],
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        })
+        .remainingAccounts(proofAccounts)
+        .view();
+
+      expect(verification.assetId.toBase58()).to.eq(assetId.toBase58());
+      expect(verification.owner.toBase58()).to.eq(student.publicKey.toBase58());
+      expect(verification.revoked).to.eq(false);
+      expect(verification.withinValidity).to.eq(true);
+      expect(verification.supersededBy).to.eq(null);
+      expect(verification.valid).to.eq(true);
+    });
 
-      // Try to burn with uniAuth2 (not the university authority for this cert)
+    it("verify_certificate rejects a record account of another leaf", async () => {
+      const { leaf, proofAccounts } = await leafProof(getAssetId(merkleTree.publicKey, 2));
       let threw = false;
       try {
         await program.methods
-          .burnCertificateV2({
-            root: proofData.root as number[],
-            dataHash: proofData.dataHash as number[],
-            creatorHash: proofData.creatorHash as number[],
-            nonce: new anchor.BN(proofData.nonce),
-            index: proofData.index,
-            assetDataHash: null,
-            flags: null,
-            reason: "Unauthorized burn attempt",
+          .verifyCertificate({
+            root: leaf.root,
+            owner: student.publicKey,
+            delegate: student.publicKey,
+            nonce: leaf.nonce,
+            index: leaf.index,
+            dataHash: leaf.dataHash,
+            creatorHash: leaf.creatorHash,
+            collectionHash: Array(32).fill(0),
+            assetDataHash: Array(32).fill(0),
+            flags: 0,
           })
           .accountsPartial({
-            universityAuthority: uniAuth2.publicKey,
+            globalConfig: globalPda,
+            programAllowlist: allowlistPda,
+            university: uniPda,
+            universityTree: uniTreePda,
             merkleTree: merkleTree.publicKey,
-            treeConfig: treeConfigPda,
-            coreCollection: coreCollection.publicKey,
-            mplCoreCpiSigner: mplCoreCpiSigner,
-            leafOwner: student.publicKey,
-            bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+            revocationList: revocationListPda,
+            // Leaf 1's record (revoked and superseded) instead of leaf 2's
+            certificateRecord: findCertificateRecord(getAssetId(merkleTree.publicKey, 1))[0],
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           })
           .remainingAccounts(proofAccounts)
-          .signers([uniAuth2])
-          .rpc();
+          .view();
       } catch (e: any) {
         threw = true;
-        // Could fail with Unauthorized or constraint error or inactive university
-        expect(e.message).to.match(/Unauthorized|constraint|inactive|A seeds constraint was violated/i);
+        expect(e.message).to.match(/CertificateRecordMismatch|not the record PDA/i);
       }
-      expect(threw).to.eq(true, "Expected unauthorized burn to fail");
+      expect(threw).to.eq(true, "Expected verification with another leaf's record to fail");
     });
+  });
 
-    it("fails when proof is missing (no remaining accounts)", async () => {
-      const assets = await getAssetsByOwner(student.publicKey);
-      if (assets.length === 0) {
-        console.log("No assets found - skipping missing proof test");
-        return;
-      }
+  // -------------------------------------------------------
+  // collection policy + update_certificate_metadata
+  // -------------------------------------------------------
+  describe("update_certificate_metadata", () => {
+    it("opts the collection in to mutable metadata", async () => {
+      const [policyPda] = findCollectionPolicy(coreCollection.publicKey);
+      // Default metadata rules (same as mints without a policy), mutable leaves
+      await program.methods
+        .setCollectionIssuancePolicy({
+          symbol: "GG-CERT",
+          sellerFeeBasisPoints: 0,
+          namePrefix: "",
+          nameMaxLen: 32,
+          allowedUriSchemes: 0,
+          mutableMetadata: true,
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityCollection: uniCollectionPda,
+          coreCollection: coreCollection.publicKey,
+          collectionPolicy: policyPda,
+        })
+        .signers([uniAuth])
+        .rpc();
 
-      const asset = assets[0];
-      const assetId = new PublicKey(asset.id);
-      const proofData = await getAssetWithProof(assetId);
+      const policy = await program.account.collectionPolicy.fetch(policyPda);
+      expect(policy.mutableMetadata).to.eq(true);
+      expect(policy.symbol).to.eq("GG-CERT");
+      expect(policy.collection.toBase58()).to.eq(coreCollection.publicKey.toBase58());
+    });
+
+    it("rejects updates to a certificate minted immutable", async () => {
+      // Leaf 2 was minted by reissue_certificate before the collection opted in
+      const assetId = getAssetId(merkleTree.publicKey, 2);
+      const [recordPda] = findCertificateRecord(assetId);
 
       let threw = false;
       try {
         await program.methods
-          .burnCertificateV2({
-            root: proofData.root as number[],
-            dataHash: proofData.dataHash as number[],
-            creatorHash: proofData.creatorHash as number[],
-            nonce: new anchor.BN(proofData.nonce),
-            index: proofData.index,
+          .updateCertificateMetadata({
+            root: Array(32).fill(0),
+            nonce: new anchor.BN(2),
+            index: 2,
             assetDataHash: null,
             flags: null,
-            reason: "Test burn",
+            currentName: "Test Cert (corrected)",
+            currentUri: "https://certs/test-corrected.json",
+            newName: null,
+            newUri: "ipfs://bafy-corrected",
+            newMetadataHash: metadataHashFor("ipfs://bafy-corrected"),
           })
           .accountsPartial({
             universityAuthority: uniAuth.publicKey,
+            globalConfig: globalPda,
+            programAllowlist: allowlistPda,
             merkleTree: merkleTree.publicKey,
             treeConfig: treeConfigPda,
             coreCollection: coreCollection.publicKey,
-            mplCoreCpiSigner: mplCoreCpiSigner,
+            collectionPolicy: findCollectionPolicy(coreCollection.publicKey)[0],
+            certificateRecord: recordPda,
             leafOwner: student.publicKey,
             bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            logWrapper: SPL_NOOP_PROGRAM_ID,
           })
-          // No remaining accounts - missing proof!
           .signers([uniAuth])
           .rpc();
       } catch (e: any) {
         threw = true;
-        expect(e.message).to.match(/MissingMerkleProof|Missing merkle proof/i);
+        expect(e.message).to.match(/CertificateImmutable|immutable/i);
+      }
+      expect(threw).to.eq(true, "Expected update of immutable certificate to fail");
+    });
+  });
+
+  // -------------------------------------------------------
+  // claimable certificates (escrow PDA → graduate wallet)
+  // -------------------------------------------------------
+  describe("claim_certificate", () => {
+    // One-time claim keypair handed to the graduate off-band
+    const claimKey = Keypair.generate();
+    // Leaves 0-2 are taken by earlier tests
+    const claimAssetId = () => getAssetId(merkleTree.publicKey, 3);
+
+    it("mints a claimable certificate into its escrow PDA", async () => {
+      const assetId = claimAssetId();
+      const [claimPda] = findCertificateClaim(assetId);
+      const [recordPda] = findCertificateRecord(assetId);
+
+      await program.methods
+        .mintCertificateV2(
+          mintArgs("Claimable Cert", "https://certs/claimable.json", assetId, {
+            recipient: claimPda,
+            claim: {
+              claimPubkey: claimKey.publicKey,
+              expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 86_400),
+              // Soulbound once claimed
+              transferable: false,
+            },
+          })
+        )
+        .accountsPartial(mintAccounts({ recipient: claimPda, certificateClaim: claimPda }))
+        .signers([uniAuth])
+        .rpc();
+
+      const claim = await program.account.certificateClaim.fetch(claimPda);
+      expect(claim.assetId.toBase58()).to.eq(assetId.toBase58());
+      expect(claim.claimPubkey.toBase58()).to.eq(claimKey.publicKey.toBase58());
+      expect(claim.lockOnClaim).to.eq(true);
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.recipient.toBase58()).to.eq(claimPda.toBase58());
+    });
+
+    async function claimAccounts() {
+      const assetId = claimAssetId();
+      const [claimPda] = findCertificateClaim(assetId);
+      const [recordPda] = findCertificateRecord(assetId);
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const proofData = await getAssetWithProof(assetId);
+      return {
+        leaf: {
+          root: proofData.root as number[],
+          dataHash: proofData.dataHash as number[],
+          creatorHash: proofData.creatorHash as number[],
+          nonce: new anchor.BN(proofData.nonce),
+          index: proofData.index,
+          assetDataHash: null,
+          flags: null,
+        },
+        accounts: {
+          claimant: graduate.publicKey,
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          merkleTree: merkleTree.publicKey,
+          treeConfig: treeConfigPda,
+          coreCollection: coreCollection.publicKey,
+          certificateClaim: claimPda,
+          certificateRecord: recordPda,
+          rentReceiver: uniAuth.publicKey,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+        },
+        proofAccounts: proofData.proof.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: false,
+        })) as AccountMeta[],
+        recordPda,
+      };
+    }
+
+    // Claim key signs asset_id || claimant
+    const claimSignature = (claimant: PublicKey) =>
+      Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: claimKey.secretKey,
+        message: Buffer.concat([claimAssetId().toBuffer(), claimant.toBuffer()]),
+      });
+
+    it("rejects a claim signed for another wallet", async () => {
+      await airdrop(graduate.publicKey);
+      const { leaf, accounts, proofAccounts } = await claimAccounts();
+
+      // A front-runner replays the graduate's signed claim with their own wallet
+      const attacker = Keypair.generate();
+      await airdrop(attacker.publicKey);
+
+      let threw = false;
+      try {
+        await program.methods
+          .claimCertificate(leaf)
+          .accountsPartial({ ...accounts, claimant: attacker.publicKey })
+          .remainingAccounts(proofAccounts)
+          .preInstructions([claimSignature(graduate.publicKey)])
+          .signers([attacker])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/InvalidClaimSignature|claim key/i);
+      }
+      expect(threw).to.eq(true, "Expected claim signed for another wallet to fail");
+    });
+
+    it("requires the university authority to lock the certificate on claim", async () => {
+      const { leaf, accounts, proofAccounts } = await claimAccounts();
+
+      let threw = false;
+      try {
+        await program.methods
+          .claimCertificate(leaf)
+          .accountsPartial({ ...accounts, universityAuthority: null })
+          .remainingAccounts(proofAccounts)
+          .preInstructions([claimSignature(graduate.publicKey)])
+          .signers([graduate])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/MissingUniversityAuthority|co-sign/i);
+      }
+      expect(threw).to.eq(true, "Expected a locking claim without the university authority to fail");
+    });
+
+    it("claims the certificate into the graduate's wallet and locks it", async () => {
+      const { leaf, accounts, proofAccounts, recordPda } = await claimAccounts();
+
+      await program.methods
+        .claimCertificate(leaf)
+        .accountsPartial(accounts)
+        .remainingAccounts(proofAccounts)
+        .preInstructions([claimSignature(graduate.publicKey)])
+        .signers([graduate, uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.recipient.toBase58()).to.eq(graduate.publicKey.toBase58());
+      expect(rec.isNonTransferable).to.eq(true);
+      const claim = await provider.connection.getAccountInfo(accounts.certificateClaim);
+      expect(claim).to.eq(null);
+    });
+
+    it("keeps the claimed certificate from being transferred", async () => {
+      // Leaf flags after SetNonTransferableV2: non-transferable (bit 2)
+      const { leaf, proofAccounts } = await leafProof(claimAssetId(), 4);
+      const data = Buffer.concat([
+        Buffer.from(BUBBLEGUM_TRANSFER_V2_DISCRIMINATOR),
+        Buffer.from(leaf.root),
+        Buffer.from(leaf.dataHash),
+        Buffer.from(leaf.creatorHash),
+        Buffer.from([0]), // asset_data_hash: None
+        Buffer.from([1, 4]), // flags: Some(4)
+        leaf.nonce.toArrayLike(Buffer, "le", 8),
+        new anchor.BN(leaf.index).toArrayLike(Buffer, "le", 4),
+      ]);
+      const bubblegum = new PublicKey(MPL_BUBBLEGUM_PROGRAM_ID);
+      // The graduate transfers their own leaf straight through Bubblegum TransferV2
+      const transferIx = new TransactionInstruction({
+        programId: bubblegum,
+        keys: [
+          { pubkey: treeConfigPda, isSigner: false, isWritable: true },
+          { pubkey: graduate.publicKey, isSigner: true, isWritable: true },
+          { pubkey: graduate.publicKey, isSigner: true, isWritable: false },
+          { pubkey: graduate.publicKey, isSigner: false, isWritable: false },
+          { pubkey: graduate.publicKey, isSigner: false, isWritable: false },
+          { pubkey: Keypair.generate().publicKey, isSigner: false, isWritable: false },
+          { pubkey: merkleTree.publicKey, isSigner: false, isWritable: true },
+          { pubkey: coreCollection.publicKey, isSigner: false, isWritable: false },
+          { pubkey: SPL_NOOP_PROGRAM_ID, isSigner: false, isWritable: false },
+          { pubkey: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, isSigner: false, isWritable: false },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          ...proofAccounts,
+        ],
+        data,
+      });
+
+      let threw = false;
+      try {
+        await provider.sendAndConfirm(new Transaction().add(transferIx), [graduate]);
+      } catch (e: any) {
+        threw = true;
+        expect(String(e.logs ?? e.message)).to.match(/non.?transferable/i);
       }
-      expect(threw).to.eq(true, "Expected burn without proof to fail");
+      expect(threw).to.eq(true, "Expected a transfer of the soulbound claimed leaf to fail");
+    });
+  });
+
+  // -------------------------------------------------------
+  // wallet recovery (timelocked, cancellable by the owner)
+  // -------------------------------------------------------
+  describe("recover_certificate", () => {
+    it("leaves a collection created with the recovery delegate unchanged on migration", async () => {
+      const [recoveryDelegate] = PublicKey.findProgramAddressSync(
+        [RECOVERY_DELEGATE_SEED, coreCollection.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // create_core_collection_v2_cpi already handed the plugin to the PDA: a no-op
+      await program.methods
+        .migrateRecoveryDelegate()
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityCollection: uniCollectionPda,
+          coreCollection: coreCollection.publicKey,
+          recoveryDelegate,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([uniAuth])
+        .rpc();
+    });
+
+    it("lets the current owner cancel a pending recovery", async () => {
+      const assetId = getAssetId(merkleTree.publicKey, 3);
+      const [recordPda] = findCertificateRecord(assetId);
+      const [requestPda] = PublicKey.findProgramAddressSync(
+        [RECOVERY_SEED, assetId.toBuffer()],
+        program.programId
+      );
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      const newWallet = Keypair.generate();
+
+      await program.methods
+        .requestCertificateRecovery({ newOwner: newWallet.publicKey })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          certificateRecord: recordPda,
+          recoveryRequest: requestPda,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const req = await program.account.recoveryRequest.fetch(requestPda);
+      expect(req.oldOwner.toBase58()).to.eq(rec.recipient.toBase58());
+      expect(req.newOwner.toBase58()).to.eq(newWallet.publicKey.toBase58());
+
+      // Executing before the timelock elapses fails
+      let threw = false;
+      try {
+        await program.methods
+          .recoverCertificate({
+            root: Array(32).fill(0),
+            dataHash: Array(32).fill(0),
+            creatorHash: Array(32).fill(0),
+            nonce: new anchor.BN(3),
+            index: 3,
+            assetDataHash: null,
+            flags: null,
+          })
+          .accountsPartial({
+            universityAuthority: uniAuth.publicKey,
+            globalConfig: globalPda,
+            programAllowlist: allowlistPda,
+            merkleTree: merkleTree.publicKey,
+            treeConfig: treeConfigPda,
+            coreCollection: coreCollection.publicKey,
+            certificateRecord: recordPda,
+            recoveryRequest: requestPda,
+            leafOwner: rec.recipient,
+            newLeafOwner: newWallet.publicKey,
+            bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            logWrapper: SPL_NOOP_PROGRAM_ID,
+          })
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/TimelockNotElapsed|Timelock/i);
+      }
+      expect(threw).to.eq(true, "Expected recovery before timelock to fail");
+
+      // The real owner (the graduate who claimed leaf 3) blocks it
+      expect(rec.recipient.toBase58()).to.eq(graduate.publicKey.toBase58());
+      await program.methods
+        .cancelCertificateRecovery()
+        .accountsPartial({
+          canceller: graduate.publicKey,
+          recoveryRequest: requestPda,
+          rentReceiver: uniAuth.publicKey,
+        })
+        .signers([graduate])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(requestPda);
+      expect(closed).to.eq(null);
+    });
+
+    it("moves the soulbound certificate to the new wallet after the timelock", async () => {
+      const assetId = getAssetId(merkleTree.publicKey, 3);
+      const [recordPda] = findCertificateRecord(assetId);
+      const [requestPda] = PublicKey.findProgramAddressSync(
+        [RECOVERY_SEED, assetId.toBuffer()],
+        program.programId
+      );
+      const [recoveryDelegate] = PublicKey.findProgramAddressSync(
+        [RECOVERY_DELEGATE_SEED, coreCollection.publicKey.toBuffer()],
+        program.programId
+      );
+      const newWallet = Keypair.generate();
+      // Leaf 3 was locked soulbound when the graduate claimed it
+      expect((await program.account.certificateRecord.fetch(recordPda)).isNonTransferable).to.eq(true);
+
+      await program.methods
+        .requestCertificateRecovery({ newOwner: newWallet.publicKey })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          certificateRecord: recordPda,
+          recoveryRequest: requestPda,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      // Localnet builds use the 10-second timelock (short-recovery-timelock)
+      const req = await program.account.recoveryRequest.fetch(requestPda);
+      expect(req.eta.sub(req.requestedAt).toNumber()).to.eq(10);
+      const waitMs = (req.eta.toNumber() - Math.floor(Date.now() / 1000) + 2) * 1000;
+      await new Promise((resolve) => setTimeout(resolve, Math.max(waitMs, 2000)));
+
+      const proofData = await getAssetWithProof(assetId);
+      await program.methods
+        .recoverCertificate({
+          root: proofData.root as number[],
+          dataHash: proofData.dataHash as number[],
+          creatorHash: proofData.creatorHash as number[],
+          nonce: new anchor.BN(proofData.nonce),
+          index: proofData.index,
+          assetDataHash: null,
+          // Leaf 3 was locked soulbound on claim
+          flags: 4,
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          merkleTree: merkleTree.publicKey,
+          treeConfig: treeConfigPda,
+          coreCollection: coreCollection.publicKey,
+          recoveryDelegate,
+          certificateRecord: recordPda,
+          recoveryRequest: requestPda,
+          leafOwner: graduate.publicKey,
+          newLeafOwner: newWallet.publicKey,
+          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          proofData.proof.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: false,
+          })) as AccountMeta[]
+        )
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.recipient.toBase58()).to.eq(newWallet.publicKey.toBase58());
+      expect(rec.isNonTransferable).to.eq(true);
+      const closed = await provider.connection.getAccountInfo(requestPda);
+      expect(closed).to.eq(null);
+    });
+  });
+
+  // -------------------------------------------------------
+  // student consent policy (co-sign or MintConsent PDA)
+  // -------------------------------------------------------
+  describe("student consent", () => {
+    const name = "MSc Data Science — 2025";
+    const uri = "https://certs/consent.json";
+    // Leaves 0-3 are taken by earlier tests
+    const consentAssetId = () => getAssetId(merkleTree.publicKey, 4);
+
+    async function setConsentPolicy(requireStudentConsent: boolean) {
+      await program.methods
+        .setStudentConsentPolicy({ requireStudentConsent })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+        })
+        .signers([uniAuth])
+        .rpc();
+    }
+
+    function mintWithConsent(mintConsent: PublicKey | null) {
+      const assetId = consentAssetId();
+      return program.methods
+        .mintCertificateV2(mintArgs(name, uri, assetId))
+        .accountsPartial(mintAccounts({ mintConsent }))
+        .signers([uniAuth]);
+    }
+
+    it("rejects a mint without consent once the policy is on", async () => {
+      await setConsentPolicy(true);
+      const uni = await program.account.university.fetch(uniPda);
+      expect(uni.requireStudentConsent).to.eq(true);
+
+      // Up-to-date accounts are left as they are by the layout migration
+      await program.methods
+        .migrateUniversity()
+        .accountsPartial({
+          superAdmin: superAdmin.publicKey,
+          globalConfig: globalPda,
+          universityAuthority: uniAuth.publicKey,
+          university: uniPda,
+        })
+        .signers([superAdmin])
+        .rpc();
+      const migrated = await program.account.university.fetch(uniPda);
+      expect(migrated.requireStudentConsent).to.eq(true);
+
+      let threw = false;
+      try {
+        await mintWithConsent(null).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/StudentConsentRequired|consent is required/i);
+      }
+      expect(threw).to.eq(true, "Expected mint without consent to fail");
+    });
+
+    it("mints against a MintConsent the student created", async () => {
+      const credentialHash = nameUriHash(name, uri);
+      const [consentPda] = findMintConsent(uniPda, student.publicKey, credentialHash);
+
+      await program.methods
+        .giveMintConsent({ credentialHash })
+        .accountsPartial({
+          student: student.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          mintConsent: consentPda,
+        })
+        .signers([student])
+        .rpc();
+
+      await mintWithConsent(consentPda).rpc();
+
+      const consent = await program.account.mintConsent.fetch(consentPda);
+      expect(consent.assetId?.toBase58()).to.eq(consentAssetId().toBase58());
+
+      await setConsentPolicy(false);
+    });
+  });
+
+  // -------------------------------------------------------
+  // joint degrees (co-issuers sign or pre-approve)
+  // -------------------------------------------------------
+  describe("joint degrees", () => {
+    const partnerAuth = Keypair.generate();
+    const [partnerPda] = PublicKey.findProgramAddressSync(
+      [UNIVERSITY_SEED, partnerAuth.publicKey.toBuffer()],
+      program.programId
+    );
+    const name = "Joint MSc Finance — 2025";
+    const uri = "https://certs/joint.json";
+    const credentialHash = nameUriHash(name, uri);
+    // Leaves 0-4 are taken by earlier tests
+    const jointAssetId = () => getAssetId(merkleTree.publicKey, 5);
+    const approvalPda = () =>
+      PublicKey.findProgramAddressSync(
+        [CO_ISSUANCE_SEED, partnerPda.toBuffer(), uniPda.toBuffer(), Buffer.from(credentialHash)],
+        program.programId
+      )[0];
+
+    function mintJoint(coIssuers: PublicKey[], coIssuerAccounts: AccountMeta[]) {
+      return program.methods
+        .mintCertificateV2(mintArgs(name, uri, jointAssetId(), { coIssuers }))
+        .accountsPartial(mintAccounts())
+        .remainingAccounts(coIssuerAccounts)
+        .signers([uniAuth]);
+    }
+
+    it("registers a partner university that pre-approves the joint degree", async () => {
+      await airdrop(partnerAuth.publicKey);
+      await program.methods
+        .registerUniversity({ name: "Partner U", metadataUri: "https://u/3.json" })
+        .accountsPartial({
+          universityAuthority: partnerAuth.publicKey,
+          globalConfig: globalPda,
+          university: partnerPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([partnerAuth])
+        .rpc();
+      await program.methods
+        .approveUniversity()
+        .accountsPartial({
+          superAdmin: superAdmin.publicKey,
+          globalConfig: globalPda,
+          universityAuthority: partnerAuth.publicKey,
+          university: partnerPda,
+        })
+        .signers([superAdmin])
+        .rpc();
+
+      await program.methods
+        .approveCoIssuance({ credentialHash })
+        .accountsPartial({
+          universityAuthority: partnerAuth.publicKey,
+          globalConfig: globalPda,
+          university: partnerPda,
+          partner: uniPda,
+          coIssuanceApproval: approvalPda(),
+        })
+        .signers([partnerAuth])
+        .rpc();
+
+      const approval = await program.account.coIssuanceApproval.fetch(approvalPda());
+      expect(approval.partner.toBase58()).to.eq(uniPda.toBase58());
+      expect(approval.assetId).to.eq(null);
+    });
+
+    it("rejects an inactive co-issuer", async () => {
+      let threw = false;
+      try {
+        await mintJoint([uniPda2], [
+          { pubkey: uniPda2, isSigner: false, isWritable: false },
+          { pubkey: uniAuth2.publicKey, isSigner: false, isWritable: false },
+        ]).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/UniversityInactive|inactive/i);
+      }
+      expect(threw).to.eq(true, "Expected mint with an inactive co-issuer to fail");
+    });
+
+    it("rejects a pre-approval passed read-only", async () => {
+      // The partner does not sign; its approval must be writable to be consumed
+      let threw = false;
+      try {
+        await mintJoint([partnerPda], [
+          { pubkey: partnerPda, isSigner: false, isWritable: false },
+          { pubkey: approvalPda(), isSigner: false, isWritable: false },
+        ]).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/CoIssuanceNotApproved|not approved/i);
+      }
+      expect(threw).to.eq(true, "Expected mint with a read-only co-issuance approval to fail");
+      const approval = await program.account.coIssuanceApproval.fetch(approvalPda());
+      expect(approval.assetId).to.eq(null);
+    });
+
+    it("mints a joint degree listing the partner as co-issuer", async () => {
+      await mintJoint([partnerPda], [
+        { pubkey: partnerPda, isSigner: false, isWritable: false },
+        { pubkey: approvalPda(), isSigner: false, isWritable: true },
+      ]).rpc();
+
+      const [recordPda] = findCertificateRecord(jointAssetId());
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.coIssuers.length).to.eq(1);
+      expect(rec.coIssuers[0].university.toBase58()).to.eq(partnerPda.toBase58());
+      expect(rec.coIssuers[0].verified).to.eq(false);
+
+      const approval = await program.account.coIssuanceApproval.fetch(approvalPda());
+      expect(approval.assetId?.toBase58()).to.eq(jointAssetId().toBase58());
+
+      // Validity checks every co-issuer is active
+      await program.methods
+        .verifyCertificateValidity()
+        .accountsPartial({ certificateRecord: recordPda })
+        .remainingAccounts([{ pubkey: partnerPda, isSigner: false, isWritable: false }])
+        .rpc();
+    });
+  });
+
+  // -------------------------------------------------------
+  // collection issuance policy (symbol, naming, URI schemes)
+  // -------------------------------------------------------
+  describe("collection issuance policy", () => {
+    // Leaves 0-5 are taken by earlier tests
+    const policyAssetId = () => getAssetId(merkleTree.publicKey, 6);
+    const policyPda = () => findCollectionPolicy(coreCollection.publicKey)[0];
+
+    async function setPolicy(args: {
+      symbol: string;
+      namePrefix: string;
+      allowedUriSchemes: number;
+      mutableMetadata: boolean;
+    }) {
+      await program.methods
+        .setCollectionIssuancePolicy({ ...args, sellerFeeBasisPoints: 0, nameMaxLen: 32 })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityCollection: uniCollectionPda,
+          coreCollection: coreCollection.publicKey,
+          collectionPolicy: policyPda(),
+        })
+        .signers([uniAuth])
+        .rpc();
+    }
+
+    function mintUnderPolicy(name: string, uri: string) {
+      return program.methods
+        .mintCertificateV2(mintArgs(name, uri, policyAssetId()))
+        .accountsPartial(mintAccounts({ collectionPolicy: policyPda() }))
+        .signers([uniAuth]);
+    }
+
+    async function expectMintError(name: string, uri: string, pattern: RegExp) {
+      let threw = false;
+      try {
+        await mintUnderPolicy(name, uri).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(pattern);
+      }
+      expect(threw).to.eq(true, `Expected mint of "${name}" (${uri}) to fail`);
+    }
+
+    it("sets a per-collection symbol, name prefix and URI schemes", async () => {
+      // ipfs | ar
+      await setPolicy({ symbol: "UOM-BSC", namePrefix: "UOM ", allowedUriSchemes: 3, mutableMetadata: false });
+
+      const policy = await program.account.collectionPolicy.fetch(policyPda());
+      expect(policy.symbol).to.eq("UOM-BSC");
+      expect(policy.namePrefix).to.eq("UOM ");
+      expect(policy.allowedUriSchemes).to.eq(3);
+    });
+
+    it("rejects names and URIs outside the policy", async () => {
+      await expectMintError("BSc Physics", "ipfs://bafy-physics", /NameNotAllowedByPolicy|collection policy/i);
+      await expectMintError("UOM BSc Physics", "https://certs/physics.json", /UriSchemeNotAllowed|scheme/i);
+    });
+
+    it("rejects a mint that passes another account instead of the policy", async () => {
+      // Skipping the policy used to be possible by leaving the account out
+      let threw = false;
+      try {
+        await program.methods
+          .mintCertificateV2(mintArgs("BSc Physics", "https://certs/physics.json", policyAssetId()))
+          .accountsPartial(mintAccounts({ collectionPolicy: Keypair.generate().publicKey }))
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/ConstraintSeeds|seeds constraint/i);
+      }
+      expect(threw).to.eq(true, "Expected a mint without the collection policy to fail");
+    });
+
+    it("mints with the collection's symbol", async () => {
+      await mintUnderPolicy("UOM BSc Physics", "ipfs://bafy-physics").rpc();
+
+      const [recordPda] = findCertificateRecord(policyAssetId());
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.symbol).to.eq("UOM-BSC");
+      expect(rec.isMutable).to.eq(false);
+
+      // Restore the defaults used by the rest of the suite
+      await setPolicy({ symbol: "GG-CERT", namePrefix: "", allowedUriSchemes: 0, mutableMetadata: true });
+    });
+  });
+
+  // -------------------------------------------------------
+  // metadata URI scheme allowlist (GlobalConfig)
+  // -------------------------------------------------------
+  describe("metadata URI allowlist", () => {
+    async function setSchemes(allowedUriSchemes: number) {
+      await program.methods
+        .setAllowedUriSchemes({ allowedUriSchemes })
+        .accountsPartial({ superAdmin: superAdmin.publicKey, globalConfig: globalPda })
+        .signers([superAdmin])
+        .rpc();
+    }
+
+    it("rejects metadata URIs whose scheme is not allowlisted", async () => {
+      // ipfs | ar only
+      await setSchemes(3);
+      const config = await program.account.globalConfig.fetch(globalPda);
+      expect(config.allowedUriSchemes).to.eq(3);
+
+      // An up-to-date config keeps its schemes through the layout migration
+      await program.methods
+        .migrateGlobalConfig()
+        .accountsPartial({ superAdmin: superAdmin.publicKey, globalConfig: globalPda })
+        .signers([superAdmin])
+        .rpc();
+      const migrated = await program.account.globalConfig.fetch(globalPda);
+      expect(migrated.allowedUriSchemes).to.eq(3);
+
+      const uri = "https://certs/mutable.json";
+      let threw = false;
+      try {
+        await program.methods
+          .mintCertificateV2(mintArgs("BSc Mutable Host", uri, getAssetId(merkleTree.publicKey, 7)))
+          .accountsPartial(mintAccounts())
+          .signers([uniAuth])
+          .rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/UriSchemeNotAllowed|scheme/i);
+      }
+      expect(threw).to.eq(true, "Expected an https:// mint to fail");
+
+      // ipfs | ar | https
+      await setSchemes(7);
+    });
+  });
+
+  // -------------------------------------------------------
+  // mint_certificates_batch (leaves 7 and 8)
+  // -------------------------------------------------------
+  describe("mint_certificates_batch", () => {
+    // sha256 of the backend BatchIssuanceJob id
+    const batchId = Array.from(createHash("sha256").update("clx0batchjob0000000000001").digest());
+
+    it("mints several certificates and emits a batch summary", async () => {
+      const entries = [
+        { recipient: student.publicKey, name: "BSc Chemistry", uri: "ipfs://bafy-chem" },
+        { recipient: graduate.publicKey, name: "BSc Biology", uri: "ipfs://bafy-bio" },
+      ].map((e) => ({
+        ...e,
+        metadataHash: metadataHashFor(e.uri),
+        credentialId: credentialIdFor(`batch-${e.name}`),
+        transferable: true,
+      }));
+      const assetIds = [7, 8].map((i) => getAssetId(merkleTree.publicKey, i));
+
+      // No consents: the program id stands in for the optional MintConsent
+      const sig = await program.methods
+        .mintCertificatesBatch({ batchId, chunkIndex: 0, proofLen: 0, entries })
+        .accountsPartial(batchMintAccounts())
+        .remainingAccounts(
+          entries.flatMap((e, i) => [
+            { pubkey: e.recipient, isSigner: false, isWritable: false },
+            { pubkey: findCertificateRecord(assetIds[i])[0], isSigner: false, isWritable: true },
+            { pubkey: findCredentialId(uniPda, e.credentialId)[0], isSigner: false, isWritable: true },
+            { pubkey: program.programId, isSigner: false, isWritable: false },
+          ])
+        )
+        .signers([uniAuth])
+        .rpc({ commitment: "confirmed" });
+
+      for (const [i, e] of entries.entries()) {
+        const rec = await program.account.certificateRecord.fetch(findCertificateRecord(assetIds[i])[0]);
+        expect(rec.recipient.toBase58()).to.eq(e.recipient.toBase58());
+        expect(rec.assetId.toBase58()).to.eq(assetIds[i].toBase58());
+        expect(rec.nonce.toNumber()).to.eq(7 + i);
+        expect(rec.metadataHash).to.deep.eq(e.metadataHash);
+        const mapping = await program.account.credentialId.fetch(findCredentialId(uniPda, e.credentialId)[0]);
+        expect(mapping.assetId.toBase58()).to.eq(assetIds[i].toBase58());
+      }
+
+      // One CertificateMintedV2 per entry, then the batch summary
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      const events = [...parser.parseLogs(tx!.meta!.logMessages!)];
+      expect(events.filter((ev) => ev.name === "certificateMintedV2")).to.have.length(2);
+      const summary = events.find((ev) => ev.name === "certificateBatchMinted")!.data as any;
+      expect(summary.batchId).to.deep.eq(batchId);
+      expect(summary.firstNonce.toNumber()).to.eq(7);
+      expect(summary.count).to.eq(2);
+    });
+  });
+
+  // -------------------------------------------------------
+  // idempotent minting via credential ids (leaf 9)
+  // -------------------------------------------------------
+  describe("credential id idempotency", () => {
+    const credentialId = credentialIdFor("cred_clx0alice0000000000002");
+    const mappingPda = () => findCredentialId(uniPda, credentialId)[0];
+
+    function mintWithCredentialId(leaf: number) {
+      const uri = "ipfs://bafy-msc-data";
+      return program.methods
+        .mintCertificateV2(
+          mintArgs("MSc Data Science", uri, getAssetId(merkleTree.publicKey, leaf), { credentialId })
+        )
+        .accountsPartial(mintAccounts({ credentialIdRecord: mappingPda() }))
+        .signers([uniAuth]);
+    }
+
+    it("maps the credential id to the minted asset", async () => {
+      await mintWithCredentialId(9).rpc();
+
+      const mapping = await program.account.credentialId.fetch(mappingPda());
+      expect(mapping.university.toBase58()).to.eq(uniPda.toBase58());
+      expect(mapping.credentialId).to.deep.eq(credentialId);
+      expect(mapping.assetId.toBase58()).to.eq(getAssetId(merkleTree.publicKey, 9).toBase58());
+    });
+
+    it("rejects a second mint of the same credential id", async () => {
+      let threw = false;
+      try {
+        await mintWithCredentialId(10).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/already in use|custom program error: 0x0/i);
+      }
+      expect(threw).to.eq(true, "Expected the duplicate mint to fail");
+
+      // The failed retry left the tree untouched: leaf 10 is still unminted
+      const info = await provider.connection.getAccountInfo(findCertificateRecord(getAssetId(merkleTree.publicKey, 10))[0]);
+      expect(info).to.eq(null);
+    });
+  });
+
+  // -------------------------------------------------------
+  // graduating cohorts (leaf 10)
+  // -------------------------------------------------------
+  describe("cohorts", () => {
+    const programme = "BSc Computer Science";
+    const year = 2025;
+    const cohortPda = () => findCohort(uniPda, programme, year)[0];
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+    // Same construction as Cohort::current_root: depth 16, leaf = sha256(asset_id), zero-padded
+    function cohortRoot(assetIds: PublicKey[]): number[] {
+      let level = assetIds.map((id) => sha256(id.toBuffer()));
+      let zero = Buffer.alloc(32);
+      for (let h = 0; h < 16; h++) {
+        const next: Buffer[] = [];
+        for (let i = 0; i < level.length; i += 2) {
+          next.push(sha256(level[i], level[i + 1] ?? zero));
+        }
+        level = next.length ? next : [sha256(zero, zero)];
+        zero = sha256(zero, zero);
+      }
+      return Array.from(level[0]);
+    }
+
+    function mintIntoCohort(leaf: number) {
+      const uri = "ipfs://bafy-bsc-cs-2025";
+      return program.methods
+        .mintCertificateV2(
+          mintArgs("BSc Computer Science 2025", uri, getAssetId(merkleTree.publicKey, leaf))
+        )
+        .accountsPartial(mintAccounts({ cohort: cohortPda() }))
+        .signers([uniAuth]);
+    }
+
+    it("creates a cohort and counts the certificates minted into it", async () => {
+      await program.methods
+        .createCohort({
+          programme,
+          year,
+          conferralDate: new anchor.BN(1_751_328_000),
+          expectedCount: 120,
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          cohort: cohortPda(),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      await mintIntoCohort(10).rpc();
+
+      const cohort = await program.account.cohort.fetch(cohortPda());
+      expect(cohort.programme).to.eq(programme);
+      expect(cohort.expectedCount).to.eq(120);
+      expect(cohort.issuedCount).to.eq(1);
+      expect(cohort.revokedCount).to.eq(0);
+
+      const rec = await program.account.certificateRecord.fetch(
+        findCertificateRecord(getAssetId(merkleTree.publicKey, 10))[0]
+      );
+      expect(rec.cohort!.toBase58()).to.eq(cohortPda().toBase58());
+    });
+
+    it("seals the cohort with a root over its asset ids", async () => {
+      await program.methods
+        .sealCohort()
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          cohort: cohortPda(),
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const cohort = await program.account.cohort.fetch(cohortPda());
+      expect(cohort.sealed).to.eq(true);
+      expect(cohort.root).to.deep.eq(cohortRoot([getAssetId(merkleTree.publicKey, 10)]));
+
+      let threw = false;
+      try {
+        await mintIntoCohort(11).rpc();
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(/CohortSealed|sealed/i);
+      }
+      expect(threw).to.eq(true, "Expected a mint into a sealed cohort to fail");
+    });
+  });
+
+  // -------------------------------------------------------
+  // anchor-only issuance (credential batch roots)
+  // -------------------------------------------------------
+  describe("anchored credential batches", () => {
+    const batchId = Array.from(createHash("sha256").update("clx0microcreds000000000001").digest());
+    const batchPda = () =>
+      PublicKey.findProgramAddressSync(
+        [CREDENTIAL_BATCH_SEED, uniPda.toBuffer(), Buffer.from(batchId)],
+        program.programId
+      )[0];
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+    // Three course completions, padded with a zero leaf to a tree of four
+    const leaves = ["intro-rust", "solana-101", "zk-basics"].map((c) => sha256(Buffer.from(c)));
+    const padded = [...leaves, Buffer.alloc(32)];
+    const n01 = sha256(padded[0], padded[1]);
+    const n23 = sha256(padded[2], padded[3]);
+    const root = Array.from(sha256(n01, n23));
+
+    function verify(leaf: Buffer, index: number, proof: Buffer[]) {
+      return program.methods
+        .verifyAnchoredCredential({
+          leaf: Array.from(leaf),
+          index,
+          proof: proof.map((p) => Array.from(p)),
+        })
+        .accountsPartial({ credentialBatch: batchPda(), university: uniPda })
+        .view();
+    }
+
+    it("anchors a batch root and verifies inclusion proofs", async () => {
+      await program.methods
+        .anchorCredentialBatch({ batchId, root, count: 3, schemaId: "course-completion/v1" })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          credentialBatch: batchPda(),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const batch = await program.account.credentialBatch.fetch(batchPda());
+      expect(batch.count).to.eq(3);
+      expect(batch.schemaId).to.eq("course-completion/v1");
+
+      const ok = await verify(leaves[2], 2, [padded[3], n01]);
+      expect(ok.included).to.eq(true);
+      expect(ok.valid).to.eq(true);
+
+      // Wrong position, or an inner node posing as a leaf
+      expect((await verify(leaves[2], 3, [padded[3], n01])).included).to.eq(false);
+      expect((await verify(n01, 0, [n23])).included).to.eq(false);
+    });
+
+    it("revokes the whole batch", async () => {
+      await program.methods
+        .revokeCredentialBatch({ reason: { issuedInError: {} }, note: "Wrong course catalogue" })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          superAdmin: null,
+          globalConfig: globalPda,
+          university: uniPda,
+          credentialBatch: batchPda(),
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const res = await verify(leaves[0], 0, [padded[1], n23]);
+      expect(res.included).to.eq(true);
+      expect(res.revoked).to.eq(true);
+      expect(res.valid).to.eq(false);
+    });
+  });
+
+  // -------------------------------------------------------
+  // on-chain Groth16 verification (ach_member_v2 + proof requests)
+  // -------------------------------------------------------
+  describe("zk achievement proofs", () => {
+    // Generated for the certificate at leaf 9 with the zk-service's single-party v2 dev
+    // key; see scripts/export-fixture.js there
+    const readJson = (...p: string[]) => JSON.parse(readFileSync(path.join(...p), "utf8"));
+    const artifacts = path.join(__dirname, "..", "..", "..", "zk-service", "artifacts");
+    const devArtifacts = path.join(__dirname, "..", "..", "..", "zk-service", "dev-artifacts");
+    const fixture = readJson(__dirname, "fixtures", "ach_member_v2_proof.json");
+    const v1Key = readJson(artifacts, "ach_member_v1_vkey.json");
+    const v2Key = readJson(devArtifacts, "ach_member_v2_vkey.json");
+    const BN254_R = new anchor.BN("21888242871839275222246405745257275088548364400416034343698204186575808495617");
+    const be32 = (v: string) => new anchor.BN(v).toArray("be", 32);
+    const g1 = (p: string[]) => [...be32(p[0]), ...be32(p[1])];
+    // alt_bn128 wants c1 before c0 for each G2 coordinate
+    const g2 = (p: string[][]) => [...be32(p[0][1]), ...be32(p[0][0]), ...be32(p[1][1]), ...be32(p[1][0])];
+
+    // alpha_g1 || beta_g2 || gamma_g2 || delta_g2 || ic[..]
+    const keyData = (vkey: any) =>
+      Buffer.from([
+        ...g1(vkey.vk_alpha_1),
+        ...g2(vkey.vk_beta_2),
+        ...g2(vkey.vk_gamma_2),
+        ...g2(vkey.vk_delta_2),
+        ...vkey.IC.flatMap((p: string[]) => g1(p)),
+      ]);
+    const keyPda = (version: number) =>
+      PublicKey.findProgramAddressSync(
+        [VERIFYING_KEY_SEED, superAdmin.publicKey.toBuffer(), Buffer.from("ach_member"), new anchor.BN(version).toArrayLike(Buffer, "le", 2)],
+        program.programId
+      )[0];
+    const adminAccounts = (version: number) => ({ superAdmin: superAdmin.publicKey, verifyingKey: keyPda(version) });
+
+    // The fixture proofs answer requests of this verifier, so its key is fixed
+    const verifier = Keypair.fromSeed(createHash("sha256").update("genuinegrads-test-verifier").digest());
+    const nonceOf = (label: string) => Array.from(createHash("sha256").update(label).digest());
+    const firstNonce = nonceOf("employer-acme-v2-first");
+    const secondNonce = nonceOf("employer-acme-v2-second");
+    const requestPda = (nonce: number[]) =>
+      PublicKey.findProgramAddressSync(
+        [PROOF_REQUEST_SEED, verifier.publicKey.toBuffer(), Buffer.from(nonce)],
+        program.programId
+      )[0];
+    const nullifierPda = (nullifier: number[]) =>
+      PublicKey.findProgramAddressSync([NULLIFIER_SEED, Buffer.from(nullifier)], program.programId)[0];
+
+    // publicSignals = [nullifier, commitment, credential_hash, achievement_hash, request_context]
+    const proofArgs = (entry: any) => {
+      const [nullifier, commitment, credentialHash, achievementHash] = entry.publicSignals.map(be32);
+      return {
+        version: 2,
+        proofA: g1(entry.proof.pi_a),
+        proofB: g2(entry.proof.pi_b),
+        proofC: g1(entry.proof.pi_c),
+        commitment,
+        credentialHash,
+        achievementHash,
+        nullifier,
+      };
+    };
+    const args = proofArgs(fixture.proofs[0]);
+    const secondArgs = proofArgs(fixture.proofs[1]);
+    const { commitment, achievementHash } = args;
+
+    const assetId = () => getAssetId(merkleTree.publicKey, 9);
+    const commitmentPda = (c: number[]) =>
+      PublicKey.findProgramAddressSync(
+        [ACHIEVEMENT_COMMITMENT_SEED, assetId().toBuffer(), Buffer.from(c)],
+        program.programId
+      )[0];
+    const register = (c: number[]) =>
+      program.methods
+        .registerAchievementCommitment({ commitment: c, achievementHash })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          certificateRecord: findCertificateRecord(assetId())[0],
+          achievementCommitment: commitmentPda(c),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([uniAuth]);
+    const createRequest = (nonce: number[], expiresAt: anchor.BN | null) =>
+      program.methods
+        .createProofRequest({ nonce, achievementHash, expiresAt })
+        .accountsPartial({
+          verifier: verifier.publicKey,
+          proofRequest: requestPda(nonce),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([verifier]);
+    const verify = (a: typeof args, nonce: number[]) =>
+      program.methods
+        .verifyAchievementProof(a)
+        .accountsPartial({
+          verifier: verifier.publicKey,
+          verifyingKey: keyPda(a.version),
+          achievementCommitment: commitmentPda(a.commitment),
+          certificateRecord: findCertificateRecord(assetId())[0],
+          university: uniPda,
+          proofRequest: requestPda(nonce),
+          nullifierRecord: nullifierPda(a.nullifier),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([verifier]);
+
+    async function publishKey(version: number, vkey: any, beforeFinalize?: () => Promise<void>) {
+      const data = keyData(vkey);
+      await program.methods
+        .createVerifyingKey({
+          circuitId: "ach_member",
+          version,
+          publicInputs: vkey.nPublic,
+          keyHash: Array.from(createHash("sha256").update(data).digest()),
+        })
+        .accountsPartial({
+          superAdmin: superAdmin.publicKey,
+          globalConfig: globalPda,
+          verifyingKey: keyPda(version),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([superAdmin])
+        .rpc();
+
+      for (let offset = 0; offset < data.length; offset += 400) {
+        await program.methods
+          .writeVerifyingKeyChunk({ offset, chunk: data.subarray(offset, offset + 400) })
+          .accountsPartial(adminAccounts(version))
+          .signers([superAdmin])
+          .rpc();
+      }
+
+      if (beforeFinalize) await beforeFinalize();
+
+      await program.methods
+        .finalizeVerifyingKey()
+        .accountsPartial(adminAccounts(version))
+        .signers([superAdmin])
+        .rpc();
+
+      const vk = await program.account.verifyingKey.fetch(keyPda(version));
+      expect(vk.finalized).to.eq(true);
+      expect(Buffer.from(vk.data).equals(data)).to.eq(true);
+      return data;
+    }
+
+    async function verifiedEvent(sig: string) {
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      return [...parser.parseLogs(tx!.meta!.logMessages!)].find((e) => e.name === "zkProofVerified")!.data as any;
+    }
+
+    async function expectError(p: Promise<unknown>, re: RegExp, what: string) {
+      let threw = false;
+      try {
+        await p;
+      } catch (e: any) {
+        threw = true;
+        expect(e.message).to.match(re);
+      }
+      expect(threw).to.eq(true, what);
+    }
+
+    before(async () => {
+      await airdrop(verifier.publicKey);
+    });
+
+    it("refuses to publish an ach_member key without the proof request inputs", async () => {
+      // v1 has no nullifier or request_context, so its proofs could be replayed forever
+      await expectError(
+        program.methods
+          .createVerifyingKey({
+            circuitId: "ach_member",
+            version: 1,
+            publicInputs: v1Key.nPublic,
+            keyHash: Array.from(createHash("sha256").update(keyData(v1Key)).digest()),
+          })
+          .accountsPartial({
+            superAdmin: superAdmin.publicKey,
+            globalConfig: globalPda,
+            verifyingKey: keyPda(1),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([superAdmin])
+          .rpc(),
+        /InvalidVerifyingKey|verifying key/i,
+        "Expected a v1 key to be refused"
+      );
+    });
+
+    it("registers the student's commitment on the certificate", async () => {
+      expect(assetId().toBase58()).to.eq(fixture.inputs.credential, "fixture was made for another asset id");
+
+      await register(commitment).rpc();
+
+      const entry = await program.account.achievementCommitment.fetch(commitmentPda(commitment));
+      expect(entry.university.toBase58()).to.eq(uniPda.toBase58());
+      expect(entry.assetId.toBase58()).to.eq(assetId().toBase58());
+      expect(entry.achievementHash).to.deep.eq(achievementHash);
+
+      await expectError(register(Array(32).fill(0xff)).rpc(), /InvalidPublicInput|scalar field/i,
+        "Expected a non-canonical commitment to be rejected");
+    });
+
+    it("opens requests whose context is derived from their address", async () => {
+      const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      await createRequest(firstNonce, expiresAt).rpc();
+      await createRequest(secondNonce, null).rpc();
+
+      expect([requestPda(firstNonce), requestPda(secondNonce)].map((k) => k.toBase58())).to.deep.eq(
+        fixture.inputs.proof_requests,
+        "fixture was made for other proof requests"
+      );
+
+      const request = await program.account.proofRequest.fetch(requestPda(firstNonce));
+      expect(request.verifier.toBase58()).to.eq(verifier.publicKey.toBase58());
+      expect(request.achievementHash).to.deep.eq(achievementHash);
+      const context = new anchor.BN(createHash("sha256").update(requestPda(firstNonce).toBuffer()).digest())
+        .umod(BN254_R)
+        .toArray("be", 32);
+      expect(request.context).to.deep.eq(context);
+      expect(be32(fixture.proofs[0].publicSignals[4])).to.deep.eq(context);
+      expect(request.verifiedCount).to.eq(0);
+    });
+
+    it("rejects a request that is already expired", async () => {
+      await expectError(createRequest(nonceOf("employer-acme-stale"), new anchor.BN(1)).rpc(),
+        /InvalidProofRequest|Proof request mismatch/i, "Expected an expired request to be rejected");
+    });
+
+    it("publishes the ach_member v2 key in chunks and finalizes it", async () => {
+      const data = await publishKey(2, v2Key, () =>
+        expectError(verify(args, firstNonce).rpc(), /VerifyingKeyNotFinalized|not been finalized/i,
+          "Expected verification against an unfinalized key to fail")
+      );
+
+      await expectError(
+        program.methods
+          .writeVerifyingKeyChunk({ offset: data.length, chunk: Buffer.alloc(64) })
+          .accountsPartial(adminAccounts(2))
+          .signers([superAdmin])
+          .rpc(),
+        /VerifyingKeyFinalized|finalized/i,
+        "Expected a write to a finalized key to fail"
+      );
+    });
+
+    it("rejects a proof submitted by someone other than the request's verifier", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
+      await expectError(
+        verify(args, firstNonce).accountsPartial({ verifier: stranger.publicKey }).signers([stranger]).rpc(),
+        /InvalidProofRequest|Proof request mismatch/i,
+        "Expected a proof submitted by another wallet to fail"
+      );
+    });
+
+    it("rejects inputs that don't match the registered achievement", async () => {
+      const other = new anchor.BN(createHash("sha256").update("honours_2024").digest())
+        .umod(BN254_R)
+        .toArray("be", 32);
+      await expectError(verify({ ...args, achievementHash: other }, firstNonce).rpc(),
+        /CommitmentMismatch|registered commitment/i, "Expected a proof for an unregistered achievement to fail");
+    });
+
+    it("rejects a forged proof", async () => {
+      await expectError(verify({ ...args, proofA: args.proofC }, firstNonce).rpc(), /InvalidProof|verification failed/i,
+        "Expected a forged proof to fail");
+    });
+
+    it("rejects a proof made for another request", async () => {
+      await expectError(verify(args, secondNonce).rpc(), /InvalidProof|verification failed/i,
+        "Expected a proof to fail against another request's context");
+    });
+
+    it("verifies a fixture proof, spends its nullifier and emits ZkProofVerified", async () => {
+      const ev = await verifiedEvent(await verify(args, firstNonce).rpc({ commitment: "confirmed" }));
+      expect(ev.circuitId).to.eq("ach_member");
+      expect(ev.version).to.eq(2);
+      expect(ev.keyDeprecated).to.eq(false);
+      expect(ev.admin.toBase58()).to.eq(superAdmin.publicKey.toBase58());
+      expect(ev.assetId.toBase58()).to.eq(assetId().toBase58());
+      expect(ev.commitment).to.deep.eq(commitment);
+      expect(ev.achievementHash).to.deep.eq(achievementHash);
+      expect(ev.proofRequest.toBase58()).to.eq(requestPda(firstNonce).toBase58());
+      expect(ev.nullifier).to.deep.eq(args.nullifier);
+
+      const used = await program.account.nullifier.fetch(nullifierPda(args.nullifier));
+      expect(used.nullifier).to.deep.eq(args.nullifier);
+      expect(used.proofRequest.toBase58()).to.eq(requestPda(firstNonce).toBase58());
+      expect(used.achievementCommitment.toBase58()).to.eq(commitmentPda(commitment).toBase58());
+
+      const request = await program.account.proofRequest.fetch(requestPda(firstNonce));
+      expect(request.verifiedCount).to.eq(1);
+    });
+
+    it("rejects a second spend of the same nullifier", async () => {
+      await expectError(verify(args, firstNonce).rpc(), /already in use|custom program error: 0x0/i,
+        "Expected a replayed proof to fail");
+
+      const request = await program.account.proofRequest.fetch(requestPda(firstNonce));
+      expect(request.verifiedCount).to.eq(1);
+    });
+
+    it("keeps verifying against a deprecated key", async () => {
+      await program.methods
+        .deprecateVerifyingKey()
+        .accountsPartial(adminAccounts(2))
+        .signers([superAdmin])
+        .rpc();
+
+      // Another request gives an unlinkable nullifier for the same commitment
+      expect(secondArgs.commitment).to.deep.eq(commitment);
+      expect(secondArgs.nullifier).to.not.deep.eq(args.nullifier);
+
+      const ev = await verifiedEvent(await verify(secondArgs, secondNonce).rpc({ commitment: "confirmed" }));
+      expect(ev.keyDeprecated).to.eq(true);
+      expect(ev.proofRequest.toBase58()).to.eq(requestPda(secondNonce).toBase58());
+    });
+  });
+
+  // -------------------------------------------------------
+  // soulbound minting (default) and locking existing leaves
+  // -------------------------------------------------------
+  describe("soulbound certificates", () => {
+    it("mints soulbound unless the certificate is marked transferable", async () => {
+      const assetId = getAssetId(merkleTree.publicKey, await nextLeafIndex());
+
+      await program.methods
+        .mintCertificateV2(
+          mintArgs("BSc Mathematics — 2025", "https://certs/soulbound.json", assetId, { transferable: false })
+        )
+        .accountsPartial(mintAccounts())
+        .remainingAccounts(await nextLeafProof())
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(findCertificateRecord(assetId)[0]);
+      expect(rec.isNonTransferable).to.eq(true);
+      expect(rec.recipient.toBase58()).to.eq(student.publicKey.toBase58());
+    });
+
+    it("locks a transferable certificate with set_non_transferable_v2", async () => {
+      const assetId = getAssetId(merkleTree.publicKey, await nextLeafIndex());
+      const [recordPda] = findCertificateRecord(assetId);
+
+      await program.methods
+        .mintCertificateV2(mintArgs("BSc Statistics — 2025", "https://certs/lockable.json", assetId))
+        .accountsPartial(mintAccounts())
+        .signers([uniAuth])
+        .rpc();
+      expect((await program.account.certificateRecord.fetch(recordPda)).isNonTransferable).to.eq(false);
+
+      const { leaf, proofAccounts } = await leafProof(assetId);
+      await program.methods
+        .setNonTransferableV2({ ...leaf, assetId })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityCollection: uniCollectionPda,
+          universityTree: uniTreePda,
+          merkleTree: merkleTree.publicKey,
+          treeConfig: treeConfigPda,
+          coreCollection: coreCollection.publicKey,
+          certificateRecord: recordPda,
+          leafOwner: student.publicKey,
+          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(proofAccounts)
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.isNonTransferable).to.eq(true);
+    });
+  });
+  // -------------------------------------------------------
+  // update_certificate_metadata on a mutable leaf (appended after the fixed leaf indices)
+  // -------------------------------------------------------
+  describe("mutable certificate metadata", () => {
+    it("updates the uri and extends the metadata hash chain", async () => {
+      const [policyPda] = findCollectionPolicy(coreCollection.publicKey);
+      // Keep the naming and URI rules of the issuance policy test, opt in to mutable metadata
+      await program.methods
+        .setCollectionIssuancePolicy({
+          symbol: "UOM-BSC",
+          namePrefix: "UOM ",
+          allowedUriSchemes: 3,
+          mutableMetadata: true,
+          sellerFeeBasisPoints: 0,
+          nameMaxLen: 32,
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          university: uniPda,
+          universityCollection: uniCollectionPda,
+          coreCollection: coreCollection.publicKey,
+          collectionPolicy: policyPda,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const name = "UOM BSc Chemistry";
+      const uri = "ipfs://bafy-chemistry";
+      const newUri = "ipfs://bafy-chemistry-v2";
+      const assetId = getAssetId(merkleTree.publicKey, await nextLeafIndex());
+      const [recordPda] = findCertificateRecord(assetId);
+      await program.methods
+        .mintCertificateV2(mintArgs(name, uri, assetId))
+        .accountsPartial(mintAccounts({ collectionPolicy: policyPda }))
+        .signers([uniAuth])
+        .rpc();
+
+      let rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.isMutable).to.eq(true);
+      expect(rec.metadataVersion).to.eq(0);
+      expect(rec.metadataChain).to.deep.eq(Array(32).fill(0));
+
+      const { leaf, proofAccounts } = await leafProof(assetId);
+      await program.methods
+        .updateCertificateMetadata({
+          root: leaf.root,
+          nonce: leaf.nonce,
+          index: leaf.index,
+          assetDataHash: null,
+          flags: null,
+          currentName: name,
+          currentUri: uri,
+          newName: null,
+          newUri,
+          newMetadataHash: metadataHashFor(newUri),
+        })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          programAllowlist: allowlistPda,
+          merkleTree: merkleTree.publicKey,
+          treeConfig: treeConfigPda,
+          coreCollection: coreCollection.publicKey,
+          collectionPolicy: policyPda,
+          certificateRecord: recordPda,
+          leafOwner: student.publicKey,
+          bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+        })
+        .remainingAccounts(proofAccounts)
+        .signers([uniAuth])
+        .rpc();
+
+      // chain = sha256(previous chain || name_uri_hash of the replaced metadata)
+      const expectedChain = Array.from(
+        createHash("sha256")
+          .update(Buffer.concat([Buffer.alloc(32), Buffer.from(nameUriHash(name, uri))]))
+          .digest()
+      );
+      rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.metadataVersion).to.eq(1);
+      expect(rec.metadataChain).to.deep.eq(expectedChain);
+      expect(rec.nameUriHash).to.deep.eq(nameUriHash(name, newUri));
+      expect(rec.metadataHash).to.deep.eq(metadataHashFor(newUri));
+    });
+  });
+
+  // -------------------------------------------------------
+  // renew_certificate on an expiring certificate
+  // -------------------------------------------------------
+  describe("renew_certificate", () => {
+    it("extends the expiry of a time-limited certificate", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const validUntil = new anchor.BN(now + 86_400);
+      const renewedUntil = new anchor.BN(now + 365 * 86_400);
+      const assetId = getAssetId(merkleTree.publicKey, await nextLeafIndex());
+      const [recordPda] = findCertificateRecord(assetId);
+
+      await program.methods
+        .mintCertificateV2(
+          mintArgs("First Aid Certificate", "https://certs/first-aid.json", assetId, { validUntil })
+        )
+        .accountsPartial(mintAccounts())
+        .signers([uniAuth])
+        .rpc();
+
+      await program.methods
+        .renewCertificate({ validUntil: renewedUntil })
+        .accountsPartial({
+          universityAuthority: uniAuth.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          certificateRecord: recordPda,
+        })
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.validUntil.toNumber()).to.eq(renewedUntil.toNumber());
+      expect(rec.renewalCount).to.eq(1);
+
+      await program.methods
+        .verifyCertificateValidity()
+        .accountsPartial({ certificateRecord: recordPda })
+        .view();
+    });
+  });
+
+  // -------------------------------------------------------
+  // mint_certificates_batch parity: soulbound, consent, pre-funded PDAs
+  // -------------------------------------------------------
+  describe("mint_certificates_batch parity", () => {
+    it("mints a soulbound entry against a MintConsent into pre-funded PDAs", async () => {
+      const name = "BSc Physics";
+      const uri = "ipfs://bafy-physics";
+      const credentialId = credentialIdFor("batch-soulbound-physics");
+      const assetId = getAssetId(merkleTree.publicKey, await nextLeafIndex());
+      const [recordPda] = findCertificateRecord(assetId);
+      const [idPda] = findCredentialId(uniPda, credentialId);
+
+      const credentialHash = nameUriHash(name, uri);
+      const [consentPda] = findMintConsent(uniPda, student.publicKey, credentialHash);
+      await program.methods
+        .giveMintConsent({ credentialHash })
+        .accountsPartial({
+          student: student.publicKey,
+          globalConfig: globalPda,
+          university: uniPda,
+          mintConsent: consentPda,
+        })
+        .signers([student])
+        .rpc();
+
+      // Anyone can send lamports to the predictable PDAs ahead of the batch
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: recordPda, lamports: 1_000_000 }),
+          SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: idPda, lamports: 1_000_000 })
+        )
+      );
+
+      const proof = await nextLeafProof();
+      await program.methods
+        .mintCertificatesBatch({
+          batchId: Array.from(createHash("sha256").update("clx0batchjob0000000000002").digest()),
+          chunkIndex: 0,
+          proofLen: proof.length,
+          entries: [
+            {
+              recipient: student.publicKey,
+              name,
+              uri,
+              metadataHash: metadataHashFor(uri),
+              credentialId,
+              transferable: false,
+            },
+          ],
+        })
+        .accountsPartial(batchMintAccounts())
+        .remainingAccounts([
+          { pubkey: student.publicKey, isSigner: false, isWritable: false },
+          { pubkey: recordPda, isSigner: false, isWritable: true },
+          { pubkey: idPda, isSigner: false, isWritable: true },
+          { pubkey: consentPda, isSigner: false, isWritable: true },
+          ...proof,
+        ])
+        .signers([uniAuth])
+        .rpc();
+
+      const rec = await program.account.certificateRecord.fetch(recordPda);
+      expect(rec.isNonTransferable).to.eq(true);
+      expect(rec.recipient.toBase58()).to.eq(student.publicKey.toBase58());
+      const mapping = await program.account.credentialId.fetch(idPda);
+      expect(mapping.assetId.toBase58()).to.eq(assetId.toBase58());
+      const consent = await program.account.mintConsent.fetch(consentPda);
+      expect(consent.assetId!.toBase58()).to.eq(assetId.toBase58());
     });
   });
 });
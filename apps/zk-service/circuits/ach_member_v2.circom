@@ -0,0 +1,67 @@
+pragma circom 2.1.6;
+
+include "../node_modules/circomlib/circuits/poseidon.circom";
+
+/*
+ * Achievement Membership Circuit v2
+ *
+ * Same statement as v1, bound to a verifier's proof request so a proof can't be
+ * replayed: the request context is a public input, and the circuit outputs a
+ * nullifier that the Solana program marks as used.
+ *
+ * Public Output:
+ *   - nullifier: Poseidon(student_secret, salt, request_context)
+ *
+ * Public Inputs:
+ *   - commitment: The stored Poseidon commitment C
+ *   - credential_hash: Hash of the credential ID (Solana mintAddress)
+ *   - achievement_hash: Hash of the achievement code
+ *   - request_context: Context of the on-chain ProofRequest
+ *
+ * Private Inputs:
+ *   - student_secret: 254-bit secret known only to student
+ *   - salt: 254-bit random salt for additional entropy
+ *
+ * Constraint:
+ *   Poseidon(credential_hash, student_secret, salt, achievement_hash) === commitment
+ *
+ * The nullifier is unique per (commitment, request) and reveals nothing about the
+ * secret, so proofs for different requests can't be linked through it.
+ *
+ * snarkjs public signals: [nullifier, commitment, credential_hash, achievement_hash, request_context]
+ */
+
+template AchievementMembershipV2() {
+    // Public inputs
+    signal input commitment;
+    signal input credential_hash;
+    signal input achievement_hash;
+    signal input request_context;
+
+    // Private inputs (witness)
+    signal input student_secret;
+    signal input salt;
+
+    // Public output
+    signal output nullifier;
+
+    // Same commitment as v1
+    component poseidon = Poseidon(4);
+    poseidon.inputs[0] <== credential_hash;
+    poseidon.inputs[1] <== student_secret;
+    poseidon.inputs[2] <== salt;
+    poseidon.inputs[3] <== achievement_hash;
+
+    commitment === poseidon.out;
+
+    // Nullifier bound to the proof request
+    component nullifier_hash = Poseidon(3);
+    nullifier_hash.inputs[0] <== student_secret;
+    nullifier_hash.inputs[1] <== salt;
+    nullifier_hash.inputs[2] <== request_context;
+
+    nullifier <== nullifier_hash.out;
+}
+
+// Main component with public inputs specified
+component main {public [commitment, credential_hash, achievement_hash, request_context]} = AchievementMembershipV2();
@@ -5,12 +5,15 @@
 
 set -e
 
+# Circuit to build (ach_member_v1, ach_member_v2)
+CIRCUIT="${CIRCUIT:-ach_member_v1}"
+
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 ROOT_DIR="$(dirname "$SCRIPT_DIR")"
 CIRCUITS_DIR="$ROOT_DIR/circuits"
 BUILD_DIR="$ROOT_DIR/build"
 
-echo "🔧 Compiling ach_member_v1 circuit..."
+echo "🔧 Compiling ${CIRCUIT} circuit..."
 
 # Create build directory if it doesn't exist
 mkdir -p "$BUILD_DIR"
@@ -19,7 +22,7 @@ mkdir -p "$BUILD_DIR"
 # --r1cs: Generate R1CS constraint system
 # --wasm: Generate WASM for witness generation
 # --sym: Generate symbol file for debugging
-circom "$CIRCUITS_DIR/ach_member_v1.circom" \
+circom "$CIRCUITS_DIR/${CIRCUIT}.circom" \
     --r1cs \
     --wasm \
     --sym \
@@ -29,11 +32,11 @@ circom "$CIRCUITS_DIR/ach_member_v1.circom" \
 echo "✅ Circuit compiled successfully!"
 echo ""
 echo "Generated files:"
-echo "  - $BUILD_DIR/ach_member_v1.r1cs"
-echo "  - $BUILD_DIR/ach_member_v1.sym"
-echo "  - $BUILD_DIR/ach_member_v1_js/ach_member_v1.wasm"
+echo "  - $BUILD_DIR/${CIRCUIT}.r1cs"
+echo "  - $BUILD_DIR/${CIRCUIT}.sym"
+echo "  - $BUILD_DIR/${CIRCUIT}_js/${CIRCUIT}.wasm"
 echo ""
 
 # Show circuit info
 echo "📊 Circuit info:"
-npx snarkjs r1cs info "$BUILD_DIR/ach_member_v1.r1cs"
+npx snarkjs r1cs info "$BUILD_DIR/${CIRCUIT}.r1cs"
@@ -0,0 +1,99 @@
+// Generate the ach_member_v2 proof fixture used by the Solana program tests
+// (apps/program/genuinegrads/tests/fixtures/ach_member_v2_proof.json), with the dev key.
+// Proofs are randomized, so each run writes a different but equally valid proof.
+//
+//   node scripts/export-fixture.js [asset id] [proof request...]
+
+import { buildPoseidon } from 'circomlibjs';
+import * as snarkjs from 'snarkjs';
+import * as crypto from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+// The program only verifies ach_member_v2 (proof requests); v1 keys are refused
+const CIRCUIT = 'ach_member_v2';
+
+// Single-party dev key; the program tests publish its vkey
+const ARTIFACTS_DIR = path.join(__dirname, '..', 'dev-artifacts');
+const WASM_FILE = path.join(ARTIFACTS_DIR, `${CIRCUIT}.wasm`);
+const ZKEY_FILE = path.join(ARTIFACTS_DIR, `${CIRCUIT}.zkey`);
+const VKEY_FILE = path.join(ARTIFACTS_DIR, `${CIRCUIT}_vkey.json`);
+const FIXTURE_FILE = path.join(
+  __dirname, '..', '..', 'program', 'genuinegrads', 'tests', 'fixtures', `${CIRCUIT}_proof.json`
+);
+
+// BN254 scalar field order
+const BN254_SCALAR_FIELD = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
+
+// Fixed test inputs; the program tests derive the tampered cases from these.
+// The credential is the asset id of leaf 9 in the program tests' fixed merkle tree,
+// because the program checks credential_hash against the certificate it verifies.
+const INPUTS = {
+  credential: process.argv[2] || '45WxrRC8gPcmHJGz4DSTiUN2kWEMgW4qWJQub1bBRTa9',
+  achievement: 'deans_list_2024',
+  student_secret: 'fixture-student-secret',
+  salt: 'fixture-salt',
+};
+
+// The ProofRequest PDAs the proofs answer. The defaults are the
+// program tests' requests (fixed verifier keypair, nonces "employer-acme-v2-first" / "-second").
+const PROOF_REQUESTS = process.argv.length > 3
+  ? process.argv.slice(3)
+  : ['5LNjHefQ3x8FghQymDjbPmWWPwCeVMFq6RvVXeCn6zXn', '6HCoYMQ6ZmHmZxcd4KMM4Cd9nvktsUrx9ocVU1KRm1yq'];
+
+// Matches stringToFieldElement in the frontend and backend
+function stringToFieldElement(input) {
+  const hash = crypto.createHash('sha256').update(input, 'utf8').digest('hex');
+  return BigInt('0x' + hash) % BN254_SCALAR_FIELD;
+}
+
+// ProofRequest.context: sha256(request address bytes) mod r
+const BASE58 = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+function requestContext(address) {
+  let value = 0n;
+  for (const c of address) value = value * 58n + BigInt(BASE58.indexOf(c));
+  const bytes = Buffer.from(value.toString(16).padStart(64, '0'), 'hex');
+  const hash = crypto.createHash('sha256').update(bytes).digest('hex');
+  return BigInt('0x' + hash) % BN254_SCALAR_FIELD;
+}
+
+const poseidon = await buildPoseidon();
+const credentialHash = stringToFieldElement(INPUTS.credential);
+const achievementHash = stringToFieldElement(INPUTS.achievement);
+const studentSecret = stringToFieldElement(INPUTS.student_secret);
+const salt = stringToFieldElement(INPUTS.salt);
+const commitment = poseidon.F.toObject(poseidon([credentialHash, studentSecret, salt, achievementHash]));
+
+const input = {
+  commitment: commitment.toString(),
+  credential_hash: credentialHash.toString(),
+  achievement_hash: achievementHash.toString(),
+  student_secret: studentSecret.toString(),
+  salt: salt.toString(),
+};
+const vkey = JSON.parse(fs.readFileSync(VKEY_FILE, 'utf8'));
+
+async function prove(extra = {}) {
+  const { proof, publicSignals } = await snarkjs.groth16.fullProve({ ...input, ...extra }, WASM_FILE, ZKEY_FILE);
+  if (!(await snarkjs.groth16.verify(vkey, publicSignals, proof))) {
+    throw new Error('Generated proof does not verify');
+  }
+  return { proof, publicSignals };
+}
+
+// One proof per request, in order
+const proofs = [];
+for (const request of PROOF_REQUESTS) {
+  proofs.push(await prove({ request_context: requestContext(request).toString() }));
+}
+const fixture = { circuit: CIRCUIT, inputs: { ...INPUTS, proof_requests: PROOF_REQUESTS }, proofs };
+
+fs.mkdirSync(path.dirname(FIXTURE_FILE), { recursive: true });
+fs.writeFileSync(FIXTURE_FILE, JSON.stringify(fixture, null, 2) + '\n');
+
+console.log(`✅ Fixture written to ${FIXTURE_FILE}`);
+process.exit(0);
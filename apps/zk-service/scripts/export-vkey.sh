@@ -4,12 +4,16 @@
 
 set -e
 
+# Circuit to build (ach_member_v1, ach_member_v2)
+CIRCUIT="${CIRCUIT:-ach_member_v1}"
+
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 ROOT_DIR="$(dirname "$SCRIPT_DIR")"
-ARTIFACTS_DIR="$ROOT_DIR/artifacts"
+# Output directory: artifacts (production) or dev-artifacts (single-party dev keys)
+ARTIFACTS_DIR="$ROOT_DIR/${ARTIFACTS_SUBDIR:-artifacts}"
 
-ZKEY_FILE="$ARTIFACTS_DIR/ach_member_v1.zkey"
-VKEY_FILE="$ARTIFACTS_DIR/ach_member_v1_vkey.json"
+ZKEY_FILE="$ARTIFACTS_DIR/${CIRCUIT}.zkey"
+VKEY_FILE="$ARTIFACTS_DIR/${CIRCUIT}_vkey.json"
 
 echo "📤 Exporting verification key..."
 
@@ -5,13 +5,17 @@
 
 set -e
 
+# Circuit to build (ach_member_v1, ach_member_v2)
+CIRCUIT="${CIRCUIT:-ach_member_v1}"
+
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 ROOT_DIR="$(dirname "$SCRIPT_DIR")"
 BUILD_DIR="$ROOT_DIR/build"
 PTAU_DIR="$ROOT_DIR/ptau"
-ARTIFACTS_DIR="$ROOT_DIR/artifacts"
+# Output directory: artifacts (production) or dev-artifacts (single-party dev keys)
+ARTIFACTS_DIR="$ROOT_DIR/${ARTIFACTS_SUBDIR:-artifacts}"
 
-R1CS_FILE="$BUILD_DIR/ach_member_v1.r1cs"
+R1CS_FILE="$BUILD_DIR/${CIRCUIT}.r1cs"
 PTAU_FILE="$PTAU_DIR/powersOfTau28_hez_final_14.ptau"
 
 echo "🔐 Running trusted setup..."
@@ -35,14 +39,14 @@ echo ""
 echo "🔄 Phase 2: Generating circuit-specific keys..."
 
 # Generate initial zkey (Phase 2 ceremony)
-npx snarkjs groth16 setup "$R1CS_FILE" "$PTAU_FILE" "$BUILD_DIR/ach_member_v1_0000.zkey"
+npx snarkjs groth16 setup "$R1CS_FILE" "$PTAU_FILE" "$BUILD_DIR/${CIRCUIT}_0000.zkey"
 
 # Contribute to the ceremony (adds randomness)
 # In production, multiple parties should contribute
 echo "Adding contribution to ceremony..."
 echo "genuinegrads-zk-phase1-$(date +%s)" | npx snarkjs zkey contribute \
-    "$BUILD_DIR/ach_member_v1_0000.zkey" \
-    "$BUILD_DIR/ach_member_v1_0001.zkey" \
+    "$BUILD_DIR/${CIRCUIT}_0000.zkey" \
+    "$BUILD_DIR/${CIRCUIT}_0001.zkey" \
     --name="GenuineGrads Phase 1" \
     -v
 
@@ -50,30 +54,30 @@ echo "genuinegrads-zk-phase1-$(date +%s)" | npx snarkjs zkey contribute \
 # Using a publicly verifiable random value
 BEACON="0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
 npx snarkjs zkey beacon \
-    "$BUILD_DIR/ach_member_v1_0001.zkey" \
-    "$BUILD_DIR/ach_member_v1_final.zkey" \
+    "$BUILD_DIR/${CIRCUIT}_0001.zkey" \
+    "$BUILD_DIR/${CIRCUIT}_final.zkey" \
     "$BEACON" 10 \
     --name="Final Beacon"
 
 # Verify the final zkey
 echo ""
 echo "🔍 Verifying final zkey..."
-npx snarkjs zkey verify "$R1CS_FILE" "$PTAU_FILE" "$BUILD_DIR/ach_member_v1_final.zkey"
+npx snarkjs zkey verify "$R1CS_FILE" "$PTAU_FILE" "$BUILD_DIR/${CIRCUIT}_final.zkey"
 
 # Copy final zkey to artifacts
-cp "$BUILD_DIR/ach_member_v1_final.zkey" "$ARTIFACTS_DIR/ach_member_v1.zkey"
+cp "$BUILD_DIR/${CIRCUIT}_final.zkey" "$ARTIFACTS_DIR/${CIRCUIT}.zkey"
 
 # Copy WASM to artifacts
-cp "$BUILD_DIR/ach_member_v1_js/ach_member_v1.wasm" "$ARTIFACTS_DIR/ach_member_v1.wasm"
+cp "$BUILD_DIR/${CIRCUIT}_js/${CIRCUIT}.wasm" "$ARTIFACTS_DIR/${CIRCUIT}.wasm"
 
 echo ""
 echo "✅ Trusted setup complete!"
 echo ""
 echo "Generated artifacts:"
-echo "  - $ARTIFACTS_DIR/ach_member_v1.zkey (proving key)"
-echo "  - $ARTIFACTS_DIR/ach_member_v1.wasm (witness generator)"
+echo "  - $ARTIFACTS_DIR/${CIRCUIT}.zkey (proving key)"
+echo "  - $ARTIFACTS_DIR/${CIRCUIT}.wasm (witness generator)"
 
 # Clean up intermediate files
-rm -f "$BUILD_DIR/ach_member_v1_0000.zkey"
-rm -f "$BUILD_DIR/ach_member_v1_0001.zkey"
-rm -f "$BUILD_DIR/ach_member_v1_final.zkey"
+rm -f "$BUILD_DIR/${CIRCUIT}_0000.zkey"
+rm -f "$BUILD_DIR/${CIRCUIT}_0001.zkey"
+rm -f "$BUILD_DIR/${CIRCUIT}_final.zkey"
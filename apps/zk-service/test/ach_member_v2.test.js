@@ -0,0 +1,112 @@
+import { expect } from 'chai';
+import { buildPoseidon } from 'circomlibjs';
+import * as snarkjs from 'snarkjs';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+// Development key from a single-party setup; never deploy it
+const ARTIFACTS_DIR = path.join(__dirname, '..', 'dev-artifacts');
+const WASM_FILE = path.join(ARTIFACTS_DIR, 'ach_member_v2.wasm');
+const ZKEY_FILE = path.join(ARTIFACTS_DIR, 'ach_member_v2.zkey');
+const VKEY_FILE = path.join(ARTIFACTS_DIR, 'ach_member_v2_vkey.json');
+
+// BN254 scalar field order
+const BN254_SCALAR_FIELD = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
+
+describe('Achievement Membership Circuit v2 (proof requests)', function() {
+  let poseidon;
+  let vkey;
+
+  before(async function() {
+    // Check if artifacts exist
+    if (!fs.existsSync(WASM_FILE) || !fs.existsSync(ZKEY_FILE) || !fs.existsSync(VKEY_FILE)) {
+      console.log('⚠️  Artifacts not found. Please run "npm run build:v2" first.');
+      this.skip();
+    }
+
+    poseidon = await buildPoseidon();
+    vkey = JSON.parse(fs.readFileSync(VKEY_FILE, 'utf8'));
+  });
+
+  function hash(inputs) {
+    return poseidon.F.toObject(poseidon(inputs));
+  }
+
+  function randomFieldElement() {
+    const bytes = new Uint8Array(32);
+    crypto.getRandomValues(bytes);
+    let value = 0n;
+    for (let i = 0; i < 32; i++) {
+      value = (value << 8n) | BigInt(bytes[i]);
+    }
+    return value % BN254_SCALAR_FIELD;
+  }
+
+  function circuitInput(requestContext, overrides = {}) {
+    const credentialHash = randomFieldElement();
+    const achievementHash = randomFieldElement();
+    const studentSecret = randomFieldElement();
+    const salt = randomFieldElement();
+    return {
+      commitment: hash([credentialHash, studentSecret, salt, achievementHash]).toString(),
+      credential_hash: credentialHash.toString(),
+      achievement_hash: achievementHash.toString(),
+      request_context: requestContext.toString(),
+      student_secret: studentSecret.toString(),
+      salt: salt.toString(),
+      ...overrides,
+    };
+  }
+
+  it('should output the request nullifier and verify', async function() {
+    const context = randomFieldElement();
+    const input = circuitInput(context);
+
+    const { proof, publicSignals } = await snarkjs.groth16.fullProve(input, WASM_FILE, ZKEY_FILE);
+
+    // [nullifier, commitment, credential_hash, achievement_hash, request_context]
+    const nullifier = hash([BigInt(input.student_secret), BigInt(input.salt), context]);
+    expect(publicSignals).to.deep.equal([
+      nullifier.toString(),
+      input.commitment,
+      input.credential_hash,
+      input.achievement_hash,
+      input.request_context,
+    ]);
+    expect(await snarkjs.groth16.verify(vkey, publicSignals, proof)).to.be.true;
+  });
+
+  it('should not verify against another request context', async function() {
+    const input = circuitInput(randomFieldElement());
+    const { proof, publicSignals } = await snarkjs.groth16.fullProve(input, WASM_FILE, ZKEY_FILE);
+
+    publicSignals[4] = randomFieldElement().toString();
+    expect(await snarkjs.groth16.verify(vkey, publicSignals, proof)).to.be.false;
+  });
+
+  it('should give different nullifiers for different requests', async function() {
+    const studentSecret = randomFieldElement();
+    const salt = randomFieldElement();
+    const first = hash([studentSecret, salt, randomFieldElement()]);
+    const second = hash([studentSecret, salt, randomFieldElement()]);
+    expect(first).to.not.equal(second);
+  });
+
+  it('should fail with the wrong secret', async function() {
+    const input = circuitInput(randomFieldElement());
+    try {
+      await snarkjs.groth16.fullProve(
+        { ...input, student_secret: randomFieldElement().toString() },
+        WASM_FILE,
+        ZKEY_FILE
+      );
+      expect.fail('Should have thrown an error');
+    } catch (error) {
+      expect(error).to.exist;
+    }
+  });
+});